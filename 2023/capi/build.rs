@@ -0,0 +1,216 @@
+//! Generates the entry points of the days and their C header from the same list, so the two can't
+//! drift apart. The header goes to `OUT_DIR` like the code, a test checks that the copy in
+//! `include` is the same.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+// How a part gets its input. The streaming days read from a `BufRead`, the others take a `&str`.
+enum Call {
+    Text(&'static str),
+    Stream(&'static str),
+}
+
+use Call::*;
+
+// The parts of each day, as called from within the day's module.
+const DAYS: [(u32, &[Call]); 25] = [
+    (
+        1,
+        &[
+            Stream("|input| part1(input, Policy::FirstLast)"),
+            Stream("|input| -> Result<_, Box<dyn Error>> { part2(input, &parse_words(WORDS)?, Policy::FirstLast) }"),
+        ],
+    ),
+    (
+        2,
+        &[
+            Text("|input| -> Result<_, Box<dyn Error>> { part1(&parse(input)?, &parse_bag(BAG)?) }"),
+            Text("|input| -> Result<_, Box<dyn Error>> { part2(&parse(input)?, &parse_bag(BAG)?) }"),
+        ],
+    ),
+    (3, &[Text("part1"), Text("part2")]),
+    (4, &[Stream("part1"), Stream("part2")]),
+    (
+        5,
+        &[
+            Text("|input| -> Result<_, Box<dyn Error>> { part1(&parse(input)?) }"),
+            Text("|input| -> Result<_, Box<dyn Error>> { part2(&parse(input)?) }"),
+        ],
+    ),
+    (6, &[Text("part1"), Text("part2")]),
+    (
+        7,
+        &[
+            Text("|input| -> Result<_, Box<dyn Error>> { part1(&parse(input)?) }"),
+            Text("|input| -> Result<_, Box<dyn Error>> { part2(&parse(input)?) }"),
+        ],
+    ),
+    (8, &[Text("part1"), Text("part2")]),
+    (9, &[Stream("part1"), Stream("part2")]),
+    (10, &[Text("part1"), Text("part2")]),
+    (11, &[Text("part1"), Text("part2")]),
+    (12, &[Text("part1"), Text("part2")]),
+    (13, &[Text("part1"), Text("part2")]),
+    (14, &[Text("part1"), Text("part2")]),
+    (15, &[Stream("part1"), Stream("part2")]),
+    (16, &[Text("part1"), Text("part2")]),
+    (17, &[Text("part1"), Text("part2")]),
+    (18, &[Text("part1"), Text("part2")]),
+    (19, &[Text("part1"), Text("part2")]),
+    (20, &[Text("part1"), Text("part2")]),
+    (21, &[Text("part1"), Text("part2")]),
+    (22, &[Text("part1"), Text("part2")]),
+    (23, &[Text("part1"), Text("part2")]),
+    (24, &[Stream("part1"), Stream("part2")]),
+    // The connections cut by part1 only fit one input, Karger's algorithm (with a fixed seed, so
+    // the answers can be repeated) works for all of them.
+    (25, &[Text("|input| part2(input, 0)")]),
+];
+
+// The status codes of the entry points: name, value and what it means.
+const STATUSES: [(&str, i32, &str); 6] = [
+    ("AOC_OK", 0, "The answer was written to the answer buffer."),
+    ("AOC_INVALID_ARGUMENT", 1, "A pointer was NULL."),
+    ("AOC_INVALID_INPUT", 2, "The input isn't UTF-8."),
+    (
+        "AOC_FAILED",
+        3,
+        "The solver failed, the answer buffer holds the error (as far as it fits).",
+    ),
+    (
+        "AOC_ANSWER_TOO_LONG",
+        4,
+        "The answer didn't fit into the answer buffer, including its terminating NUL.",
+    ),
+    ("AOC_PANICKED", 5, "The solver panicked."),
+];
+
+// Only day 24 needs a feature of its own.
+fn feature(day: u32) -> Option<&'static str> {
+    if day == 24 {
+        Some("day24")
+    } else {
+        None
+    }
+}
+
+// What a caller should know about the parts of a day, put above them in the header.
+fn note(day: u32) -> Option<&'static str> {
+    if day == 25 {
+        Some(
+            "aoc2023_day25_part1 runs what the day calls part2: Karger's algorithm, seeded with 0. \
+             The day's part1\n * only knows the cut of one particular input, so it isn't exported.",
+        )
+    } else {
+        None
+    }
+}
+
+fn rust(days_dir: &Path) -> String {
+    let mut code = String::new();
+    for (name, value, doc) in STATUSES {
+        writeln!(code, "/// {}\npub const {}: i32 = {};", doc, name, value).unwrap();
+    }
+
+    for (day, parts) in DAYS {
+        let cfg = match feature(day) {
+            Some(feature) => format!("#[cfg(feature = \"{}\")]\n", feature),
+            None => String::new(),
+        };
+
+        let main = days_dir
+            .join(format!("day{}", day))
+            .join("src")
+            .join("main.rs");
+        writeln!(
+            code,
+            "\n{cfg}#[allow(dead_code, clippy::all)]\nmod day{day} {{\n    include!({main:?});\n",
+        )
+        .unwrap();
+        for (part, call) in parts.iter().enumerate() {
+            let call = match call {
+                Text(f) => format!("({})(input)", f),
+                Stream(f) => format!("({})(input.as_bytes())", f),
+            };
+            writeln!(
+                code,
+                "    pub(crate) fn answer{}(input: &str) -> Result<String, String> {{\n        \
+                 {}.map(|a| a.to_string()).map_err(|e| e.to_string())\n    }}",
+                part + 1,
+                call
+            )
+            .unwrap();
+        }
+        writeln!(code, "}}").unwrap();
+
+        for part in 1..=parts.len() {
+            writeln!(
+                code,
+                "\n/// Part {part} of day {day}.\n///\n/// # Safety\n///\n/// `input` must point to `input_len` \
+                 readable bytes (or be NULL if there are none), `answer` to `answer_len` writable \
+                 ones.\n{cfg}#[no_mangle]\npub unsafe extern \"C\" fn aoc2023_day{day}_part{part}(\n    \
+                 input: *const u8,\n    input_len: usize,\n    answer: *mut c_char,\n    \
+                 answer_len: usize,\n) -> i32 {{\n    \
+                 solve(input, input_len, answer, answer_len, day{day}::answer{part})\n}}",
+            )
+            .unwrap();
+        }
+    }
+    code
+}
+
+fn header() -> String {
+    let mut header = String::from(
+        "/* Generated by build.rs, don't edit. */\n\n\
+         #ifndef AOC2023_H\n#define AOC2023_H\n\n#include <stddef.h>\n#include <stdint.h>\n\n\
+         #ifdef __cplusplus\nextern \"C\" {\n#endif\n\n",
+    );
+    for (name, value, doc) in STATUSES {
+        writeln!(header, "/* {} */\n#define {} {}", doc, name, value).unwrap();
+    }
+
+    header += "\n/*\n * Every part takes the input (input_len bytes, not NUL-terminated) and writes its answer as a\n \
+               * NUL-terminated string of at most answer_len bytes. Returns one of the status codes above.\n */\n";
+    for (day, parts) in DAYS {
+        if let Some(feature) = feature(day) {
+            writeln!(
+                header,
+                "\n/* Only if built with the {} feature. */",
+                feature
+            )
+            .unwrap();
+        }
+        if let Some(note) = note(day) {
+            writeln!(header, "\n/*\n * {}\n */", note).unwrap();
+        }
+        for part in 1..=parts.len() {
+            writeln!(
+                header,
+                "int32_t aoc2023_day{}_part{}(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);",
+                day, part
+            )
+            .unwrap();
+        }
+    }
+    header += "\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* AOC2023_H */\n";
+    header
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let manifest_dir = Path::new(&manifest_dir);
+    let days_dir = manifest_dir.parent().unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    fs::write(Path::new(&out_dir).join("days.rs"), rust(days_dir)).unwrap();
+    fs::write(Path::new(&out_dir).join("aoc2023.h"), header()).unwrap();
+
+    for (day, _) in DAYS {
+        let day_dir = days_dir.join(format!("day{}", day));
+        println!("cargo:rerun-if-changed={}", day_dir.join("src").display());
+        println!("cargo:rerun-if-changed={}", day_dir.join("rsc").display());
+    }
+}
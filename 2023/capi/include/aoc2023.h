@@ -0,0 +1,91 @@
+/* Generated by build.rs, don't edit. */
+
+#ifndef AOC2023_H
+#define AOC2023_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The answer was written to the answer buffer. */
+#define AOC_OK 0
+/* A pointer was NULL. */
+#define AOC_INVALID_ARGUMENT 1
+/* The input isn't UTF-8. */
+#define AOC_INVALID_INPUT 2
+/* The solver failed, the answer buffer holds the error (as far as it fits). */
+#define AOC_FAILED 3
+/* The answer didn't fit into the answer buffer, including its terminating NUL. */
+#define AOC_ANSWER_TOO_LONG 4
+/* The solver panicked. */
+#define AOC_PANICKED 5
+
+/*
+ * Every part takes the input (input_len bytes, not NUL-terminated) and writes its answer as a
+ * NUL-terminated string of at most answer_len bytes. Returns one of the status codes above.
+ */
+int32_t aoc2023_day1_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day1_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day2_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day2_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day3_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day3_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day4_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day4_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day5_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day5_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day6_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day6_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day7_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day7_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day8_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day8_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day9_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day9_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day10_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day10_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day11_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day11_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day12_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day12_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day13_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day13_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day14_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day14_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day15_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day15_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day16_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day16_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day17_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day17_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day18_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day18_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day19_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day19_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day20_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day20_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day21_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day21_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day22_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day22_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day23_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day23_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+
+/* Only if built with the day24 feature. */
+int32_t aoc2023_day24_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+int32_t aoc2023_day24_part2(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+
+/*
+ * aoc2023_day25_part1 runs what the day calls part2: Karger's algorithm, seeded with 0. The day's part1
+ * only knows the cut of one particular input, so it isn't exported.
+ */
+int32_t aoc2023_day25_part1(const uint8_t *input, size_t input_len, char *answer, size_t answer_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC2023_H */
@@ -0,0 +1,172 @@
+//! The 2023 solvers as a C library, for tools and tests that aren't written in Rust (like the
+//! ARM64 ports, to check their functions against). Every part of every day has an entry point
+//! `aoc2023_dayN_partP`, declared in `include/aoc2023.h` (generated by the build, like the entry
+//! points).
+//!
+//! The days are compiled into the library as they are, so a part can print what it finds on the
+//! way (like day 14's cycle) to stdout.
+
+use aoc_common::normalize;
+use std::ffi::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+// Copies the text into the buffer of the caller, NUL-terminated. Returns false if it doesn't fit.
+unsafe fn write_answer(text: &str, answer: *mut c_char, answer_len: usize) -> bool {
+    if text.len() >= answer_len {
+        return false;
+    }
+
+    ptr::copy_nonoverlapping(text.as_ptr(), answer as *mut u8, text.len());
+    *answer.add(text.len()) = 0;
+    true
+}
+
+// Runs a part on the input of the caller and writes its answer (or error) to the answer buffer.
+unsafe fn solve<F>(
+    input: *const u8,
+    input_len: usize,
+    answer: *mut c_char,
+    answer_len: usize,
+    part: F,
+) -> i32
+where
+    F: FnOnce(&str) -> Result<String, String>,
+{
+    if answer.is_null() || (input.is_null() && input_len > 0) {
+        return AOC_INVALID_ARGUMENT;
+    }
+
+    let input = if input_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AOC_INVALID_INPUT;
+    };
+    // Like the days do when they read their input.
+    let input = normalize::normalize(input);
+
+    match panic::catch_unwind(AssertUnwindSafe(|| part(&input))) {
+        Ok(Ok(text)) => {
+            if !write_answer(&text, answer, answer_len) {
+                AOC_ANSWER_TOO_LONG
+            } else {
+                AOC_OK
+            }
+        }
+        Ok(Err(error)) => {
+            // As much of the error as fits, it's only there to help.
+            let mut end = error.len().min(answer_len.saturating_sub(1));
+            while !error.is_char_boundary(end) {
+                end -= 1;
+            }
+            if answer_len > 0 {
+                write_answer(&error[..end], answer, answer_len);
+            }
+            AOC_FAILED
+        }
+        Err(_) => AOC_PANICKED,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Part = unsafe extern "C" fn(*const u8, usize, *mut c_char, usize) -> i32;
+
+    // Calls the part like a C caller would, with an answer buffer of `answer_len` bytes. Returns
+    // the status and what was written to the buffer, if anything.
+    fn call(part: Part, input: &[u8], answer_len: usize) -> (i32, String) {
+        let mut answer = vec![0xff_u8; answer_len];
+        let status = unsafe {
+            part(
+                input.as_ptr(),
+                input.len(),
+                answer.as_mut_ptr() as *mut c_char,
+                answer_len,
+            )
+        };
+        let text = match answer.iter().position(|&b| b == 0) {
+            Some(end) => String::from_utf8(answer[..end].to_vec()).unwrap(),
+            None => String::new(),
+        };
+        (status, text)
+    }
+
+    #[test]
+    fn the_header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc2023.h"));
+        assert!(
+            include_str!("../include/aoc2023.h") == generated,
+            "include/aoc2023.h differs from the one generated into OUT_DIR, copy that one over"
+        );
+    }
+
+    #[test]
+    fn answers() {
+        let input = b"1abc2\npqr3stu8vwx\n";
+        assert_eq!(call(aoc2023_day1_part1, input, 16), (AOC_OK, "50".into()));
+        // The answer and its NUL fit exactly.
+        assert_eq!(call(aoc2023_day1_part1, input, 3), (AOC_OK, "50".into()));
+        assert_eq!(
+            call(aoc2023_day1_part1, input, 2),
+            (AOC_ANSWER_TOO_LONG, String::new())
+        );
+        // Line endings are normalized like the days do.
+        let crlf = b"1abc2\r\npqr3stu8vwx\r\n";
+        assert_eq!(call(aoc2023_day1_part1, crlf, 16), (AOC_OK, "50".into()));
+    }
+
+    #[test]
+    fn arguments() {
+        let mut answer = [0xff_u8; 16];
+        let answer_ptr = answer.as_mut_ptr() as *mut c_char;
+        unsafe {
+            // No input at all is an empty one.
+            assert_eq!(aoc2023_day1_part1(ptr::null(), 0, answer_ptr, 16), AOC_OK);
+            assert_eq!(&answer[..2], b"0\0");
+            assert_eq!(
+                aoc2023_day1_part1(ptr::null(), 1, answer_ptr, 16),
+                AOC_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                aoc2023_day1_part1(b"1".as_ptr(), 1, ptr::null_mut(), 16),
+                AOC_INVALID_ARGUMENT
+            );
+        }
+        assert_eq!(
+            call(aoc2023_day1_part1, b"1abc\xff2\n", 16),
+            (AOC_INVALID_INPUT, String::new())
+        );
+    }
+
+    #[test]
+    fn errors_are_cut_where_characters_start() {
+        let (status, error) = call(aoc2023_day2_part1, "\u{e9}".as_bytes(), 256);
+        assert_eq!(status, AOC_FAILED);
+        assert!(error.ends_with("found \"\u{e9}\""), "{}", error);
+
+        // Room for the first byte of the two of the 'é', but not for the second.
+        let cut = error.find('\u{e9}').unwrap();
+        assert_eq!(
+            call(aoc2023_day2_part1, "\u{e9}".as_bytes(), cut + 2),
+            (AOC_FAILED, error[..cut].to_string())
+        );
+        assert_eq!(
+            call(aoc2023_day2_part1, "\u{e9}".as_bytes(), 1),
+            (AOC_FAILED, String::new())
+        );
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        // Too small for the start and the target of day 23's map.
+        assert_eq!(call(aoc2023_day23_part1, b"#", 16).0, AOC_PANICKED);
+    }
+}
@@ -0,0 +1,385 @@
+use aoc_common::automaton::Automaton;
+use aoc_common::cli::Args;
+use aoc_common::stream::{self, Options};
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Instant;
+
+// The words part 2 reads as numbers, unless --words names another file of them.
+const WORDS: &str = include_str!("../words.txt");
+
+// Lines "one: 1", besides comments and blank lines.
+fn parse_words(text: &str) -> Result<Vec<(String, u32)>, String> {
+    let mut words = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || {
+            format!(
+                "line {} of the words is not \"word: value\": {}",
+                number + 1,
+                line
+            )
+        };
+        let (word, value) = line.split_once(':').ok_or_else(invalid)?;
+        let value = value.trim().parse().map_err(|_| invalid())?;
+        if word.trim().is_empty() {
+            return Err(invalid());
+        }
+        words.push((word.trim().to_string(), value));
+    }
+
+    Ok(words)
+}
+
+fn digits() -> Vec<(String, u32)> {
+    (0..=9).map(|d| (d.to_string(), d)).collect()
+}
+
+// The digits and the words, for part 2.
+fn digits_and_words(words: &[(String, u32)]) -> Automaton<u32> {
+    Automaton::new(digits().into_iter().chain(words.iter().cloned()))
+}
+
+// How the calibration value of a line is made from the numbers on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Policy {
+    // The first number times ten plus the last one, as the puzzle has it.
+    FirstLast,
+    // All numbers added up.
+    Sum,
+    // All numbers written one after the other, "two1nine" is 219.
+    Concatenated,
+    // The nth number (from 1), overlapping ones included.
+    Nth(usize),
+}
+
+impl FromStr for Policy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "first-last" => Ok(Policy::FirstLast),
+            "sum" => Ok(Policy::Sum),
+            "concatenated" => Ok(Policy::Concatenated),
+            _ => match s.strip_prefix("nth:").and_then(|n| n.parse().ok()) {
+                Some(n) if n > 0 => Ok(Policy::Nth(n)),
+                _ => Err(format!("unknown policy {}", s)),
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CalibrationError {
+    NoNumbers { line: usize },
+    TooFewNumbers { line: usize, found: usize },
+    TooLarge { line: usize },
+    Overflow { line: usize },
+}
+
+impl Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalibrationError::NoNumbers { line } => write!(f, "line {} has no numbers", line),
+            CalibrationError::TooFewNumbers { line, found } => {
+                write!(f, "line {} has just {} numbers", line, found)
+            }
+            CalibrationError::TooLarge { line } => {
+                write!(
+                    f,
+                    "the numbers of line {} are too large to concatenate",
+                    line
+                )
+            }
+            CalibrationError::Overflow { line } => {
+                write!(f, "the calibration value of line {} overflows", line)
+            }
+        }
+    }
+}
+
+impl Error for CalibrationError {}
+
+impl Policy {
+    // The calibration value of the text of a line (the number of which is for errors).
+    fn value(
+        &self,
+        numbers: &Automaton<u32>,
+        text: &str,
+        line: usize,
+    ) -> Result<u32, CalibrationError> {
+        let no_numbers = || CalibrationError::NoNumbers { line };
+        // Words can have any value, so even adding two of them can overflow.
+        let overflow = || CalibrationError::Overflow { line };
+        let all = || match numbers.matches(text) {
+            matches if matches.is_empty() => Err(no_numbers()),
+            matches => Ok(matches),
+        };
+
+        match self {
+            // Doesn't need all numbers, just those at both ends.
+            Policy::FirstLast => {
+                let first = numbers.first(text).ok_or_else(no_numbers)?;
+                let last = numbers.last(text).ok_or_else(no_numbers)?;
+                first
+                    .value
+                    .checked_mul(10)
+                    .and_then(|tens| tens.checked_add(last.value))
+                    .ok_or_else(overflow)
+            }
+            Policy::Sum => all()?
+                .iter()
+                .try_fold(0u32, |sum, m| sum.checked_add(m.value))
+                .ok_or_else(overflow),
+            Policy::Concatenated => {
+                let digits: String = all()?.iter().map(|m| m.value.to_string()).collect();
+                digits
+                    .parse()
+                    .map_err(|_| CalibrationError::TooLarge { line })
+            }
+            Policy::Nth(n) => {
+                let matches = all()?;
+                match matches.get(n - 1) {
+                    Some(nth) => Ok(nth.value),
+                    None => Err(CalibrationError::TooFewNumbers {
+                        line,
+                        found: matches.len(),
+                    }),
+                }
+            }
+        }
+    }
+}
+
+// Sums up the calibration values of the lines.
+fn calibrate(
+    input: impl BufRead,
+    numbers: &Automaton<u32>,
+    policy: Policy,
+) -> Result<u64, Box<dyn Error>> {
+    // Concatenated values easily add up to more than a u32.
+    let mut total: u64 = 0;
+
+    for record in stream::lines(input) {
+        let record = record?;
+        total += policy.value(numbers, &record.text, record.start.line)? as u64;
+    }
+
+    Ok(total)
+}
+
+fn part1(input: impl BufRead, policy: Policy) -> Result<u64, Box<dyn Error>> {
+    calibrate(input, &Automaton::new(digits()), policy)
+}
+
+// Words may overlap, like "eightwo", which has an 8 first and a 2 last.
+fn part2(
+    input: impl BufRead,
+    words: &[(String, u32)],
+    policy: Policy,
+) -> Result<u64, Box<dyn Error>> {
+    calibrate(input, &digits_and_words(words), policy)
+}
+
+// The lines whose value changes when words count as numbers too, like "line 3: abcone2threexyz
+// is 22 with digits, 13 with words".
+fn disagreements(
+    input: impl BufRead,
+    words: &[(String, u32)],
+    policy: Policy,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let digits = Automaton::new(digits());
+    let digits_and_words = digits_and_words(words);
+    let mut disagreements = Vec::new();
+
+    for record in stream::lines(input) {
+        let record = record?;
+        let value = |numbers| match policy.value(numbers, &record.text, record.start.line) {
+            Ok(value) => value.to_string(),
+            Err(_) => "nothing".to_string(),
+        };
+        let (part1, part2) = (value(&digits), value(&digits_and_words));
+        if part1 != part2 {
+            disagreements.push(format!(
+                "line {}: {} is {} with digits, {} with words",
+                record.start.line, record.text, part1, part2
+            ));
+        }
+    }
+
+    Ok(disagreements)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = Args::from_env();
+    let words: Option<PathBuf> = args.value("--words")?;
+    let policy: Policy = args.value("--policy")?.unwrap_or(Policy::FirstLast);
+    let show_disagreements = args.flag("--disagreements");
+    let options = Options::new(args, include_str!("../rsc/input.txt"))?;
+    let words = match words {
+        Some(path) => parse_words(&fs::read_to_string(path)?)?,
+        None => parse_words(WORDS)?,
+    };
+
+    if show_disagreements {
+        let disagreements = disagreements(options.source.open()?, &words, policy)?;
+        for disagreement in &disagreements {
+            println!("{}", disagreement);
+        }
+        println!("{} lines disagree", disagreements.len());
+        return Ok(());
+    }
+
+    if options.runs(1) {
+        let start1 = Instant::now();
+        println!("Part 1: {}", part1(options.source.open()?, policy)?);
+        println!("Elapsed: {:.2?}\n", start1.elapsed());
+    }
+
+    if options.runs(2) {
+        let start2 = Instant::now();
+        println!("Part 2: {}", part2(options.source.open()?, &words, policy)?);
+        println!("Elapsed: {:.2?}", start2.elapsed());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE1: &str = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+
+    const SAMPLE2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn answers_of_the_samples() {
+        let words = parse_words(WORDS).unwrap();
+        assert_eq!(part1(SAMPLE1.as_bytes(), Policy::FirstLast).unwrap(), 142);
+        assert_eq!(
+            part2(SAMPLE2.as_bytes(), &words, Policy::FirstLast).unwrap(),
+            281
+        );
+    }
+
+    #[test]
+    fn words_overlap() {
+        let words = parse_words(WORDS).unwrap();
+        assert_eq!(
+            part2("eightwo\n".as_bytes(), &words, Policy::FirstLast).unwrap(),
+            82
+        );
+        assert_eq!(
+            part2("oneight\n".as_bytes(), &words, Policy::FirstLast).unwrap(),
+            18
+        );
+        assert_eq!(
+            part2("sevenine\n".as_bytes(), &words, Policy::FirstLast).unwrap(),
+            79
+        );
+    }
+
+    #[test]
+    fn words_come_from_the_configuration() {
+        let german = parse_words("# Deutsch\nnull: 0\neins: 1\nzwei: 2\ndrei: 3\n").unwrap();
+        assert_eq!(
+            part2("nulldreizwei\n".as_bytes(), &german, Policy::FirstLast).unwrap(),
+            2
+        );
+        assert_eq!(
+            part2("xeinsx7zweins\n".as_bytes(), &german, Policy::FirstLast).unwrap(),
+            11
+        );
+
+        let mut with_zero = parse_words(WORDS).unwrap();
+        with_zero.push(("zero".to_string(), 0));
+        assert_eq!(
+            part2("zerone\n".as_bytes(), &with_zero, Policy::FirstLast).unwrap(),
+            1
+        );
+
+        // Values that overflow are errors, not wrong answers (or panics).
+        let huge = parse_words("huge: 4294967295\n").unwrap();
+        for policy in [Policy::FirstLast, Policy::Sum] {
+            let error = part2("1huge\n".as_bytes(), &huge, policy).unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                "the calibration value of line 1 overflows"
+            );
+        }
+        assert_eq!(
+            part2("1huge\n".as_bytes(), &huge, Policy::Nth(2)).unwrap(),
+            4294967295
+        );
+
+        assert!(parse_words("one 1\n").is_err());
+        assert!(parse_words("one: uno\n").is_err());
+    }
+
+    #[test]
+    fn policies() {
+        let words = parse_words(WORDS).unwrap();
+        let line = "two1nine\n".as_bytes();
+        assert_eq!(part2(line, &words, Policy::Sum).unwrap(), 12);
+        assert_eq!(part2(line, &words, Policy::Concatenated).unwrap(), 219);
+        assert_eq!(part2(line, &words, Policy::Nth(2)).unwrap(), 1);
+        assert_eq!(
+            part2("eightwo\n".as_bytes(), &words, Policy::Nth(2)).unwrap(),
+            2
+        );
+        assert_eq!(
+            part1(SAMPLE1.as_bytes(), Policy::Sum).unwrap(),
+            3 + 11 + 15 + 7
+        );
+
+        assert_eq!("first-last".parse(), Ok(Policy::FirstLast));
+        assert_eq!("nth:3".parse(), Ok(Policy::Nth(3)));
+        assert!("nth:0".parse::<Policy>().is_err());
+        assert!("last".parse::<Policy>().is_err());
+    }
+
+    #[test]
+    fn lines_without_numbers_are_errors() {
+        let error = part1("1abc2\npqrstu\n".as_bytes(), Policy::FirstLast).unwrap_err();
+        assert_eq!(error.to_string(), "line 2 has no numbers");
+
+        let error = part1("1abc2\n".as_bytes(), Policy::Nth(3)).unwrap_err();
+        assert_eq!(error.to_string(), "line 1 has just 2 numbers");
+
+        let error = part1("1234567890123\n".as_bytes(), Policy::Concatenated).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "the numbers of line 1 are too large to concatenate"
+        );
+    }
+
+    #[test]
+    fn disagreements_between_digits_and_words() {
+        let words = parse_words(WORDS).unwrap();
+        let input = "1abc2\nabcone2threexyz\nsix\n".as_bytes();
+        assert_eq!(
+            disagreements(input, &words, Policy::FirstLast).unwrap(),
+            vec![
+                "line 2: abcone2threexyz is 22 with digits, 13 with words",
+                "line 3: six is nothing with digits, 66 with words",
+            ]
+        );
+    }
+}
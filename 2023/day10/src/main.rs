@@ -0,0 +1,352 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec2;
+use std::fmt::Display;
+use std::{collections::HashSet, time::Instant};
+
+#[derive(Debug)]
+enum Error {
+    MissingLoop,
+    MissingStart,
+    ParseError(ParseError),
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingLoop => write!(f, "there's no loop through the start"),
+            Error::MissingStart => write!(f, "there's no start"),
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    North = 1 << 0,
+    East = 1 << 1,
+    South = 1 << 2,
+    West = 1 << 3,
+    Start = 1 << 4,
+}
+
+trait ContainsDirection {
+    fn contains_dir(&self, dir: Direction) -> bool;
+}
+
+impl ContainsDirection for i32 {
+    fn contains_dir(&self, dir: Direction) -> bool {
+        (self & dir as i32) != 0
+    }
+}
+
+fn parse_tile(char: char) -> Option<i32> {
+    match char {
+        'S' => Some(Direction::Start as i32),
+        '|' => Some(Direction::North as i32 | Direction::South as i32),
+        '-' => Some(Direction::East as i32 | Direction::West as i32),
+        'L' => Some(Direction::North as i32 | Direction::East as i32),
+        'J' => Some(Direction::North as i32 | Direction::West as i32),
+        '7' => Some(Direction::South as i32 | Direction::West as i32),
+        'F' => Some(Direction::South as i32 | Direction::East as i32),
+        '.' => Some(0),
+        _ => None,
+    }
+}
+
+fn directions(tile: i32) -> Vec<Direction> {
+    let mut directions = Vec::new();
+
+    if tile.contains_dir(Direction::North) {
+        directions.push(Direction::North);
+    }
+    if tile.contains_dir(Direction::East) {
+        directions.push(Direction::East);
+    }
+    if tile.contains_dir(Direction::South) {
+        directions.push(Direction::South);
+    }
+    if tile.contains_dir(Direction::West) {
+        directions.push(Direction::West);
+    }
+
+    return directions;
+}
+
+struct Field {
+    field: Vec<Vec<i32>>,
+    count_x: usize,
+    count_y: usize,
+}
+
+impl Field {
+    fn new(field: Vec<Vec<i32>>) -> Field {
+        let count_y = field.len();
+        let count_x = field.first().unwrap_or(&Vec::new()).len();
+        Field {
+            field,
+            count_x,
+            count_y,
+        }
+    }
+
+    fn get(&self, coord: Vec2<usize>) -> i32 {
+        match self.field.get(coord.y) {
+            Some(row) => *row.get(coord.x).unwrap_or(&0),
+            None => 0,
+        }
+    }
+
+    fn find_start(&self) -> Option<Vec2<usize>> {
+        for (y, row) in self.field.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if tile.contains_dir(Direction::Start) {
+                    return Some(Vec2::new(x, y));
+                }
+            }
+        }
+
+        return None;
+    }
+
+    // The neighbouring coordinate in the given direction. Like the field itself, it may lie one
+    // tile beyond the east or south edge.
+    fn step(&self, coord: Vec2<usize>, direction: Direction) -> Option<Vec2<usize>> {
+        match direction {
+            Direction::North => coord.checked_sub(Vec2::new(0, 1)),
+            Direction::West => coord.checked_sub(Vec2::new(1, 0)),
+            Direction::South if coord.y < self.count_y => Some(coord + Vec2::new(0, 1)),
+            Direction::East if coord.x < self.count_x => Some(coord + Vec2::new(1, 0)),
+            _ => None,
+        }
+    }
+
+    fn start_candidates(&self, coord: Vec2<usize>) -> Vec<Vec2<usize>> {
+        let mut coords = Vec::new();
+
+        // Neighbours that connect back to the start.
+        for (direction, back) in [
+            (Direction::West, Direction::East),
+            (Direction::East, Direction::West),
+            (Direction::North, Direction::South),
+            (Direction::South, Direction::North),
+        ] {
+            if let Some(candidate) = self.step(coord, direction) {
+                if self.get(candidate).contains_dir(back) {
+                    coords.push(candidate);
+                }
+            }
+        }
+
+        return coords;
+    }
+
+    fn next_coords(&self, coord: Vec2<usize>, tile: i32) -> Vec<Vec2<usize>> {
+        directions(tile)
+            .into_iter()
+            .filter_map(|direction| self.step(coord, direction))
+            .collect()
+    }
+
+    fn find_path(
+        &self,
+        coord: Vec2<usize>,
+        from: Vec2<usize>,
+        path: &mut Vec<Vec2<usize>>,
+    ) -> bool {
+        let tile = self.get(coord);
+        if tile.contains_dir(Direction::Start) {
+            path.push(coord);
+            return true;
+        }
+
+        for next in self.next_coords(coord, tile) {
+            if next == from {
+                continue;
+            }
+
+            if self.find_path(next, coord, path) {
+                path.push(coord);
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    // Gives the start the connections to its two neighbours on the path, so the raycasting of
+    // `count_inside` sees it like any other tile of the loop.
+    fn connect_start(&mut self, start: Vec2<usize>, neighbours: [Vec2<usize>; 2]) {
+        let mut tile = Direction::Start as i32;
+        for direction in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            if let Some(coord) = self.step(start, direction) {
+                if neighbours.contains(&coord) {
+                    tile |= direction as i32;
+                }
+            }
+        }
+
+        self.field[start.y][start.x] = tile;
+    }
+
+    // Clear all tiles that are not part of the path.
+    fn clear_non_path(&mut self, path: &Vec<Vec2<usize>>) {
+        let lookup: HashSet<&Vec2<usize>> = path.iter().collect();
+
+        for (y, row) in self.field.iter_mut().enumerate() {
+            for x in 0..row.len() {
+                if !lookup.contains(&Vec2::new(x, y)) {
+                    row[x] = 0;
+                }
+            }
+        }
+    }
+
+    // Count all tiles that are inside the path (clear_non_path must have been called).
+    fn count_inside(&self) -> usize {
+        // Simple raycasting, count the intersections. Only need to consider tiles with a north or
+        // south connection (pick one, use only that one). Avoids issues with horizontal path tiles.
+        let mut sum = 0;
+
+        for row in self.field.iter() {
+            let mut is_inside = false;
+
+            for tile in row {
+                if tile.contains_dir(Direction::South) {
+                    is_inside = !is_inside;
+                } else if *tile == 0 && is_inside {
+                    sum += 1;
+                }
+            }
+        }
+
+        sum
+    }
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    let rows = parse::parse(grid("pipe", parse_tile), input).map_err(Error::ParseError)?;
+
+    let field = Field::new(rows);
+    let start = field.find_start().ok_or(Error::MissingStart)?;
+    let candidates = field.start_candidates(start);
+    for candidate in candidates {
+        let mut path = Vec::new();
+        field.find_path(candidate, start, &mut path);
+        if path.is_empty() {
+            continue;
+        }
+        return Ok((path.len() + 1) / 2);
+    }
+
+    return Err(Error::MissingLoop);
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    let rows = parse::parse(grid("pipe", parse_tile), input).map_err(Error::ParseError)?;
+
+    let mut field = Field::new(rows);
+    let start = field.find_start().ok_or(Error::MissingStart)?;
+    let candidates = field.start_candidates(start);
+    for candidate in candidates {
+        let mut path = Vec::new();
+        field.find_path(candidate, start, &mut path);
+        if path.is_empty() {
+            continue;
+        }
+
+        field.clear_non_path(&path);
+        // The path ends with the candidate and starts at the start, reached from `path[1]`.
+        field.connect_start(start, [candidate, path[1]]);
+
+        return Ok(field.count_inside());
+    }
+
+    return Err(Error::MissingLoop);
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE1: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+
+    const SAMPLE2: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+";
+
+    const SAMPLE3: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    const SAMPLE4: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+    #[test]
+    fn answers_of_the_samples() {
+        assert_eq!(part1(SAMPLE1).unwrap(), 8);
+        assert_eq!(part2(SAMPLE2).unwrap(), 4);
+        assert_eq!(part2(SAMPLE3).unwrap(), 8);
+        assert_eq!(part2(SAMPLE4).unwrap(), 10);
+    }
+}
@@ -0,0 +1,134 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec2;
+use itertools::Itertools;
+use std::fmt::Display;
+use std::{collections::HashSet, time::Instant};
+
+#[derive(Debug)]
+enum Error {
+    ParseError(ParseError),
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+fn process(input: &str, gap_size: usize) -> Result<usize, Error> {
+    let mut galaxies: Vec<Vec2<usize>> = Vec::new();
+    let mut max_x = 0;
+    let mut max_y = 0;
+
+    let image = grid("'.' or '#'", |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    });
+    let rows = parse::parse(image, input).map_err(Error::ParseError)?;
+
+    for (y, row) in rows.iter().enumerate() {
+        for (x, is_galaxy) in row.iter().enumerate() {
+            if *is_galaxy {
+                galaxies.push(Vec2::new(x, y));
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+    }
+
+    let all_x: HashSet<usize> = galaxies.iter().map(|g| g.x).collect();
+    let all_y: HashSet<usize> = galaxies.iter().map(|g| g.y).collect();
+    let mut delta_x: Vec<usize> = Vec::new();
+    let mut delta_y: Vec<usize> = Vec::new();
+
+    let mut gaps = 0;
+    for x in 0..=max_x {
+        if !all_x.contains(&x) {
+            gaps += gap_size;
+        }
+        delta_x.push(gaps);
+    }
+
+    gaps = 0;
+    for y in 0..=max_y {
+        if !all_y.contains(&y) {
+            gaps += gap_size;
+        }
+        delta_y.push(gaps);
+    }
+
+    let expanded_galaxies: Vec<Vec2<usize>> = galaxies
+        .iter()
+        .map(|g| *g + Vec2::new(delta_x[g.x], delta_y[g.y]))
+        .collect();
+
+    let sum: usize = expanded_galaxies
+        .iter()
+        .combinations(2)
+        .map(|c| c[0].manhattan(c[1]))
+        .sum();
+
+    Ok(sum)
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    return process(input, 1);
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    // Beware of Obiwan… (off-by-one)
+    return process(input, 1_000_000 - 1);
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 374);
+        assert_eq!(part2(SAMPLE).unwrap(), 82000210);
+        // The puzzle also gives the sums when empty lines count 10 and 100 times.
+        assert_eq!(process(SAMPLE, 9).unwrap(), 1030);
+        assert_eq!(process(SAMPLE, 99).unwrap(), 8410);
+    }
+}
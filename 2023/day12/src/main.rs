@@ -1,12 +1,34 @@
+use aoc_common::cli::{Args, UsageError};
+use aoc_common::parallel;
+use aoc_common::parse::{
+    self, integer, lines, many1, pair, separated, space1, symbol, tag, terminated, Input,
+    ParseError, ParseResult,
+};
+use aoc_common::stream::{Source, StreamError};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt::Display;
 use std::{collections::HashMap, time::Instant};
 
 #[derive(Debug)]
 enum Error {
-    InvalidLine,
-    InvalidCondition,
-    InvalidMatch,
+    Parse(ParseError),
+    Usage(UsageError),
+    Stream(StreamError),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Usage(e) => write!(f, "{}", e),
+            Error::Stream(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Condition {
     Good,
@@ -28,39 +50,29 @@ struct RowCursor<'a> {
 }
 
 impl Row {
-    fn new(line: &str) -> Result<Row, Error> {
-        let mut parts = line.split_whitespace();
-        let conditions = parts.next().ok_or(Error::InvalidLine)?;
-        let list = parts.next().ok_or(Error::InvalidLine)?;
-        if parts.next() != None {
-            return Err(Error::InvalidLine);
-        }
+    // "???.### 1,1,3"
+    fn parse(input: Input) -> ParseResult<Row> {
+        let condition = symbol("'.', '#' or '?'", |c| match c {
+            '.' => Some(Condition::Good),
+            '#' => Some(Condition::Damaged),
+            '?' => Some(Condition::Unknown),
+            _ => None,
+        });
+        let matches = separated(integer(), tag(","));
+        let ((springs, matches), rest) =
+            pair(terminated(many1(condition), space1()), matches)(input)?;
 
-        let springs: Result<Vec<_>, Error> = conditions
-            .chars()
-            .map(|c| match c {
-                '.' => Ok(Condition::Good),
-                '#' => Ok(Condition::Damaged),
-                '?' => Ok(Condition::Unknown),
-                _ => Err(Error::InvalidCondition),
-            })
-            .collect();
-
-        let matches: Result<Vec<usize>, Error> = list
-            .split(',')
-            .map(|s| s.parse().map_err(|_| Error::InvalidMatch))
-            .collect();
-
-        let springs = springs?;
         let spring_count = springs.len();
-        let matches = matches?;
         let match_count = matches.len();
-        Ok(Row {
-            springs,
-            matches,
-            spring_count,
-            match_count,
-        })
+        Ok((
+            Row {
+                springs,
+                matches,
+                spring_count,
+                match_count,
+            },
+            rest,
+        ))
     }
 
     fn unfold(&mut self) {
@@ -81,7 +93,7 @@ impl Row {
         self.match_count = self.matches.len();
     }
 
-    fn start(&self) -> RowCursor {
+    fn start(&self) -> RowCursor<'_> {
         return RowCursor {
             row: self,
             spring_i: 0,
@@ -152,7 +164,7 @@ impl RowCursor<'_> {
     }
 
     // Skip one spring.
-    fn skip(&self) -> RowCursor {
+    fn skip(&self) -> RowCursor<'_> {
         RowCursor {
             row: self.row,
             spring_i: self.spring_i + 1,
@@ -162,7 +174,7 @@ impl RowCursor<'_> {
 
     // Skip all good springs starting at the current position (which must be "good").
     // Returns None if the end of the spring conditions list is reached.
-    fn skip_good(&self) -> Option<RowCursor> {
+    fn skip_good(&self) -> Option<RowCursor<'_>> {
         for i in self.spring_i..self.row.spring_count {
             if self.row.springs[i] == Condition::Good {
                 continue;
@@ -179,7 +191,7 @@ impl RowCursor<'_> {
     }
 
     // Apply the current match and advance to the next match.
-    fn consume(&self) -> RowCursor {
+    fn consume(&self) -> RowCursor<'_> {
         let match_len = self.row.matches[self.match_i];
         let available = self.row.spring_count - self.spring_i;
         assert!(match_len <= available);
@@ -248,41 +260,74 @@ impl RowCursor<'_> {
     }
 }
 
-fn part1(input: &str) -> Result<(), Error> {
-    let mut sum = 0;
-    for line in input.lines() {
-        let row = Row::new(line)?;
+fn parse(input: &str) -> Result<Vec<Row>, Error> {
+    parse::parse(lines(Row::parse), input).map_err(Error::Parse)
+}
+
+// Sums up the arrangements of all rows, which are independent of each other.
+fn count_all(rows: Vec<Row>) -> usize {
+    #[cfg(feature = "parallel")]
+    let rows = rows.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let rows = rows.into_iter();
+
+    rows.map(|row| {
         let mut cache = HashMap::new();
-        sum += row.start().count(&mut cache);
-    }
-    println!("Part 1: {sum}");
-    return Ok(());
+        row.start().count(&mut cache)
+    })
+    .sum()
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    let sum = count_all(parse(input)?);
+    return Ok(sum);
 }
 
-fn part2(input: &str) -> Result<(), Error> {
-    let mut sum = 0;
-    for line in input.lines() {
-        let mut row = Row::new(line)?;
+fn part2(input: &str) -> Result<usize, Error> {
+    let mut rows = parse(input)?;
+    for row in rows.iter_mut() {
         row.unfold();
-        let mut cache = HashMap::new();
-        sum += row.start().count(&mut cache);
     }
-    println!("Part 2: {sum}");
-    return Ok(());
+    let sum = count_all(rows);
+    return Ok(sum);
 }
 
 fn main() -> Result<(), Error> {
-    let input = include_str!("../rsc/input.txt");
+    let mut args = Args::from_env();
+    parallel::init_threads(&mut args).map_err(Error::Usage)?;
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::Stream)?;
 
     let start1 = Instant::now();
-    part1(input)?;
+    println!("Part 1: {}", part1(&input)?);
     println!("Elapsed: {:.2?}\n", start1.elapsed());
 
     // The main trick for part 2 is the cache. Without the cache, the code did not find a solution within 8 hours.
     // With the cache, the solution was found in 52ms (!).
     let start2 = Instant::now();
-    part2(input)?;
+    println!("Part 2: {}", part2(&input)?);
     println!("Elapsed: {:.2?}", start2.elapsed());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 21);
+        assert_eq!(part2(SAMPLE).unwrap(), 525152);
+    }
+}
@@ -1,10 +1,28 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{self, blocks, grid, ParseError};
+use aoc_common::stream::{Source, StreamError};
+use std::fmt::Display;
 use std::time::Instant;
 
 #[derive(Debug)]
 enum Error {
     FailedToDesmudge,
+    ParseError(ParseError),
+    StreamError(StreamError),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::FailedToDesmudge => write!(f, "a pattern has no smudge"),
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 struct Map {
     mirrors: Vec<Vec<bool>>,
     is_transposed: bool,
@@ -154,76 +172,96 @@ impl Map {
     }
 }
 
-fn part1(input: &str) -> Result<(), Error> {
-    let mut acc: Vec<Vec<bool>> = Vec::new();
-    let mut result = 0;
-
-    for line in input.lines() {
-        if line.is_empty() {
-            if !acc.is_empty() {
-                let map = Map::new(acc);
-                acc = Vec::new();
-
-                let score = map.score(0);
-                assert!(score != 0);
-                result += score;
-            }
-            continue;
-        }
+fn parse(input: &str) -> Result<Vec<Map>, Error> {
+    let pattern = grid("'.' or '#'", |c| match c {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    });
+    let patterns = parse::parse(blocks(pattern), input).map_err(Error::ParseError)?;
+    Ok(patterns.into_iter().map(Map::new).collect())
+}
 
-        let row = line.chars().map(|c| c == '#').collect();
-        acc.push(row);
-    }
+fn part1(input: &str) -> Result<usize, Error> {
+    let mut result = 0;
 
-    if !acc.is_empty() {
-        let map = Map::new(acc);
+    let maps = parse(input)?;
+    for (i, map) in maps.iter().enumerate() {
         let score = map.score(0);
-        // assert!(score != 0);
+        // Not checked for the last pattern.
+        if i + 1 < maps.len() {
+            assert!(score != 0);
+        }
         result += score;
     }
 
-    println!("Part 1: {}", result);
-    return Ok(());
+    return Ok(result);
 }
 
-fn part2(input: &str) -> Result<(), Error> {
-    let mut acc: Vec<Vec<bool>> = Vec::new();
+fn part2(input: &str) -> Result<usize, Error> {
     let mut result = 0;
 
-    for line in input.lines() {
-        if line.is_empty() {
-            if !acc.is_empty() {
-                let map = Map::new(acc);
-                acc = Vec::new();
-
-                result += map.desmudged_score().ok_or(Error::FailedToDesmudge)?;
-            }
-            continue;
-        }
-
-        let row = line.chars().map(|c| c == '#').collect();
-        acc.push(row);
-    }
-
-    if !acc.is_empty() {
-        let map = Map::new(acc);
+    for map in parse(input)? {
         result += map.desmudged_score().ok_or(Error::FailedToDesmudge)?;
     }
 
-    println!("Part 2: {}", result);
-    return Ok(());
+    return Ok(result);
 }
 
 fn main() -> Result<(), Error> {
-    let input = include_str!("../rsc/input.txt");
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
 
     let start1 = Instant::now();
-    part1(input)?;
+    println!("Part 1: {}", part1(&input)?);
     println!("Elapsed: {:.2?}\n", start1.elapsed());
 
     let start2 = Instant::now();
-    part2(input)?;
+    println!("Part 2: {}", part2(&input)?);
     println!("Elapsed: {:.2?}", start2.elapsed());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // A pattern of the input, rows of '.' and '#'.
+    fn pattern() -> impl Strategy<Value = String> {
+        (1..18usize, 1..18usize).prop_flat_map(|(columns, rows)| {
+            let row = prop::collection::vec(prop::sample::select(&['.', '#'][..]), columns)
+                .prop_map(String::from_iter);
+            prop::collection::vec(row, rows).prop_map(|rows| rows.join("\n"))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn transposing_twice_is_identity(pattern in pattern()) {
+            let map = parse(&pattern).unwrap().remove(0);
+            let transposed = map.transpose();
+            let back = transposed.transpose();
+
+            prop_assert_eq!(&back.mirrors, &map.mirrors);
+            prop_assert_eq!(back.is_transposed, map.is_transposed);
+            prop_assert!(transposed.is_transposed != map.is_transposed);
+        }
+
+        #[test]
+        fn transposing_swaps_rows_and_columns(pattern in pattern()) {
+            let map = parse(&pattern).unwrap().remove(0);
+            let transposed = map.transpose();
+
+            prop_assert_eq!(transposed.mirrors.len(), map.mirrors[0].len());
+            for (y, row) in map.mirrors.iter().enumerate() {
+                for (x, mirror) in row.iter().enumerate() {
+                    prop_assert_eq!(transposed.mirrors[x][y], *mirror);
+                }
+            }
+        }
+    }
+}
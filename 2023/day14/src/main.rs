@@ -1,11 +1,27 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::stream::{Source, StreamError};
+use std::fmt::Display;
 use std::{collections::HashSet, time::Instant, usize};
 
 #[derive(Debug)]
 enum Error {
-    InvalidFieldPattern,
+    ParseError(ParseError),
+    StreamError(StreamError),
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Field {
     Empty,
     Cube,
@@ -158,49 +174,31 @@ impl Map {
     }
 }
 
-fn part1(input: &str) -> Result<(), Error> {
-    let fields: Result<Vec<Vec<Field>>, Error> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => Ok(Field::Empty),
-                    '#' => Ok(Field::Cube),
-                    'O' => Ok(Field::Round),
-                    _ => Err(Error::InvalidFieldPattern),
-                })
-                .collect()
-        })
-        .collect();
+fn parse(input: &str) -> Result<Map, Error> {
+    let platform = grid("'.', '#' or 'O'", |c| match c {
+        '.' => Some(Field::Empty),
+        '#' => Some(Field::Cube),
+        'O' => Some(Field::Round),
+        _ => None,
+    });
+    let fields = parse::parse(platform, input).map_err(Error::ParseError)?;
+    Ok(Map::new(fields))
+}
 
-    let mut map = Map::new(fields?);
+fn part1(input: &str) -> Result<usize, Error> {
+    let mut map = parse(input)?;
     map.tilt_north();
 
-    println!("Part 1: {}", map.load_north());
-    return Ok(());
+    return Ok(map.load_north());
 }
 
-fn part2(input: &str) -> Result<(), Error> {
-    let fields: Result<Vec<Vec<Field>>, Error> = input
-        .lines()
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => Ok(Field::Empty),
-                    '#' => Ok(Field::Cube),
-                    'O' => Ok(Field::Round),
-                    _ => Err(Error::InvalidFieldPattern),
-                })
-                .collect()
-        })
-        .collect();
-
-    let mut map = Map::new(fields?);
+// Runs the spin cycle `repetitions` times. Once the platform starts repeating itself, all full
+// rounds of that are skipped.
+fn spin(map: &mut Map, repetitions: usize) {
     let mut cycle_cache = HashSet::new();
     let mut cycle_start = 0;
 
     let mut i = 0;
-    let repetitions = 1000000000;
     while i < repetitions {
         map.cycle();
         if !map.cache(&mut cycle_cache) {
@@ -217,29 +215,125 @@ fn part2(input: &str) -> Result<(), Error> {
                 println!("Found cycle end at {i}, length {cycle_len}");
                 cycle_cache.clear();
 
-                // Skip all the remaining full cycles, do the the last partial cycle.
-                // (Integer division rounds down.)
-                i += ((repetitions - i) / cycle_len) * cycle_len;
+                // Skip all the remaining full cycles, do the the last partial cycle. This round
+                // is done already, hence the - 1. (Integer division rounds down.)
+                i += ((repetitions - i - 1) / cycle_len) * cycle_len;
             }
         }
 
         i += 1;
     }
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    let mut map = parse(input)?;
+    spin(&mut map, 1000000000);
 
-    println!("Part 2: {}", map.load_north());
-    return Ok(());
+    return Ok(map.load_north());
 }
 
 fn main() -> Result<(), Error> {
-    let input = include_str!("../rsc/input.txt");
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
 
     let start1 = Instant::now();
-    part1(input)?;
+    println!("Part 1: {}", part1(&input)?);
     println!("Elapsed: {:.2?}\n", start1.elapsed());
 
     let start2 = Instant::now();
-    part2(input)?;
+    println!("Part 2: {}", part2(&input)?);
     println!("Elapsed: {:.2?}", start2.elapsed());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn field() -> impl Strategy<Value = Field> {
+        prop::sample::select(&[Field::Empty, Field::Empty, Field::Cube, Field::Round][..])
+    }
+
+    fn platform() -> impl Strategy<Value = Vec<Vec<Field>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(columns, rows)| {
+            prop::collection::vec(prop::collection::vec(field(), columns), rows)
+        })
+    }
+
+    // A square platform that is its own mirror image along the diagonal from the top left.
+    fn symmetric_platform() -> impl Strategy<Value = Vec<Vec<Field>>> {
+        (1..12usize)
+            .prop_flat_map(|size| prop::collection::vec(prop::collection::vec(field(), size), size))
+            .prop_map(|fields| {
+                let size = fields.len();
+                (0..size)
+                    .map(|y| (0..size).map(|x| fields[x.min(y)][x.max(y)]).collect())
+                    .collect()
+            })
+    }
+
+    fn transposed(map: &Map) -> Vec<Field> {
+        let mut fields = Vec::new();
+        for x in 0..map.count_x {
+            for y in 0..map.count_y {
+                fields.push(*map.get(x, y));
+            }
+        }
+        fields
+    }
+
+    fn count(map: &Map, field: Field) -> usize {
+        map.fields.iter().filter(|f| **f == field).count()
+    }
+
+    proptest! {
+        #[test]
+        fn tilts_mirror_each_other(fields in symmetric_platform()) {
+            let mut north = Map::new(fields.clone());
+            north.tilt_north();
+            let mut west = Map::new(fields.clone());
+            west.tilt_west();
+            prop_assert!(transposed(&north) == west.fields);
+
+            let mut south = Map::new(fields.clone());
+            south.tilt_south();
+            let mut east = Map::new(fields);
+            east.tilt_east();
+            prop_assert!(transposed(&south) == east.fields);
+        }
+
+        #[test]
+        fn cycles_keep_the_rocks(fields in platform()) {
+            let mut map = Map::new(fields);
+            let cubes: Vec<bool> = map.fields.iter().map(|f| *f == Field::Cube).collect();
+            let round = count(&map, Field::Round);
+
+            for _ in 0..4 {
+                map.cycle();
+                prop_assert!(map.fields.iter().map(|f| *f == Field::Cube).eq(cubes.iter().cloned()));
+                prop_assert_eq!(count(&map, Field::Round), round);
+
+                // A cycle ends tilted east, so tilting east again changes nothing.
+                let before = map.fields.clone();
+                map.tilt_east();
+                prop_assert!(map.fields == before);
+            }
+        }
+
+        #[test]
+        fn skipping_repetitions_matches_cycling(fields in platform(), repetitions in 1..40usize) {
+            let mut cycled = Map::new(fields.clone());
+            for _ in 0..repetitions {
+                cycled.cycle();
+            }
+            let mut spun = Map::new(fields);
+            spin(&mut spun, repetitions);
+
+            prop_assert!(spun.fields == cycled.fields);
+        }
+    }
+}
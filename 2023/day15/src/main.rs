@@ -0,0 +1,168 @@
+use aoc_common::parse::{
+    either, integer, map, pair, preceded, tag, take_while1, Input, ParseError, ParseResult,
+};
+use aoc_common::stream::{self, Options, StreamError};
+use std::fmt::Display;
+use std::io::BufRead;
+use std::time::Instant;
+
+#[derive(Debug)]
+enum Error {
+    InvalidBoxNumber,
+    ParseError(ParseError),
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidBoxNumber => write!(f, "a box number is out of range"),
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Clone)]
+struct Lens {
+    label: String,
+    focal: usize,
+}
+
+enum Instruction<'a> {
+    Remove(&'a str),
+    Insert(&'a str, usize),
+}
+
+struct Lenses {
+    boxes: Vec<Vec<Lens>>,
+}
+
+fn hash(input: &str) -> usize {
+    input
+        .chars()
+        .fold(0, |acc, c| ((acc + c as usize) * 17) % 256)
+}
+
+impl Lenses {
+    fn new() -> Lenses {
+        Lenses {
+            boxes: vec![Vec::new(); 256],
+        }
+    }
+
+    fn process(&mut self, instruction: &Instruction) -> Result<(), Error> {
+        let (label, focal) = match *instruction {
+            Instruction::Remove(label) => {
+                let box_nr = hash(label);
+                let b = self.boxes.get_mut(box_nr).ok_or(Error::InvalidBoxNumber)?;
+                if let Some(index) = b.iter().position(|l| l.label == label) {
+                    b.remove(index);
+                }
+                return Ok(());
+            }
+            Instruction::Insert(label, focal) => (label, focal),
+        };
+
+        let lens = Lens {
+            label: label.to_string(),
+            focal,
+        };
+        let box_nr = hash(label);
+        let b = self.boxes.get_mut(box_nr).ok_or(Error::InvalidBoxNumber)?;
+        if let Some(index) = b.iter().position(|l| l.label == label) {
+            b[index] = lens;
+        } else {
+            b.push(lens);
+        }
+
+        Ok(())
+    }
+
+    fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .map(|(box_nr, b)| {
+                b.iter()
+                    .enumerate()
+                    .map(|(lens_nr, lens)| (1 + box_nr) * (lens_nr + 1) * lens.focal)
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
+fn instruction(input: Input) -> ParseResult<Instruction> {
+    let label = || take_while1("label", |c| c.is_ascii_lowercase());
+    either(
+        map(pair(label(), tag("-")), |(label, _)| {
+            Instruction::Remove(label)
+        }),
+        map(
+            pair(label(), preceded(tag("="), integer())),
+            |(label, focal)| Instruction::Insert(label, focal),
+        ),
+    )(input)
+}
+
+// The steps are comma separated, and read one at a time as the whole sequence is a single line.
+fn part1(input: impl BufRead) -> Result<usize, Error> {
+    let mut sum = 0;
+    for record in stream::records(input, b',') {
+        let record = record.map_err(Error::StreamError)?;
+        // Part 1 only hashes the steps, no matter what they mean.
+        let step = take_while1("step", |c| !c.is_whitespace());
+        sum += hash(record.parse(step).map_err(Error::ParseError)?);
+    }
+
+    return Ok(sum);
+}
+
+fn part2(input: impl BufRead) -> Result<usize, Error> {
+    let mut lenses = Lenses::new();
+    for record in stream::records(input, b',') {
+        let record = record.map_err(Error::StreamError)?;
+        let instruction = record.parse(instruction).map_err(Error::ParseError)?;
+        lenses.process(&instruction)?;
+    }
+
+    return Ok(lenses.focusing_power());
+}
+
+fn main() -> Result<(), Error> {
+    let options =
+        Options::from_args(include_str!("../rsc/input.txt")).map_err(Error::StreamError)?;
+    let open = || options.source.open().map_err(Error::StreamError);
+
+    if options.runs(1) {
+        let start1 = Instant::now();
+        println!("Part 1: {}", part1(open()?)?);
+        println!("Elapsed: {:.2?}\n", start1.elapsed());
+    }
+
+    if options.runs(2) {
+        let start2 = Instant::now();
+        println!("Part 2: {}", part2(open()?)?);
+        println!("Elapsed: {:.2?}", start2.elapsed());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE.as_bytes()).unwrap(), 1320);
+        assert_eq!(part2(SAMPLE.as_bytes()).unwrap(), 145);
+    }
+}
@@ -0,0 +1,249 @@
+use self::Direction::{East, North, South, West};
+use aoc_common::bits::{BitGrid, BitSet};
+use aoc_common::cli::{Args, UsageError};
+use aoc_common::parallel;
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec2;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt::Display;
+use std::time::Instant;
+
+#[derive(Debug)]
+enum Error {
+    Parse(ParseError),
+    Usage(UsageError),
+    Stream(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Usage(e) => write!(f, "{}", e),
+            Error::Stream(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Direction {
+    North,
+    West,
+    South,
+    East,
+}
+
+enum Field {
+    Empty,
+    MirrorSlash,
+    MirrorBackslash,
+    SplitterVertical,
+    SplitterHorizontal,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Beam {
+    pos: Vec2<usize>,
+    dir: Direction,
+}
+
+struct Contraption {
+    fields: Vec<Vec<Field>>,
+    count_x: usize,
+    count_y: usize,
+}
+
+// State of tracing the beams from one starting point. Kept apart from the contraption, so several
+// traces can run at the same time. The cycle detector has a bit for each position and direction.
+struct Trace {
+    energized: BitGrid,
+    cycle_detector: BitSet,
+}
+
+impl Contraption {
+    fn new(input: &str) -> Result<Contraption, Error> {
+        let layout = grid("'.', '/', '\\', '|' or '-'", |c| match c {
+            '.' => Some(Field::Empty),
+            '/' => Some(Field::MirrorSlash),
+            '\\' => Some(Field::MirrorBackslash),
+            '|' => Some(Field::SplitterVertical),
+            '-' => Some(Field::SplitterHorizontal),
+            _ => None,
+        });
+        let fields = parse::parse(layout, input).map_err(Error::Parse)?;
+        let count_x = fields[0].len();
+        let count_y = fields.len();
+        Ok(Contraption {
+            fields,
+            count_x,
+            count_y,
+        })
+    }
+
+    fn beam_step(&self, trace: &mut Trace, beam: &Beam) -> Vec<Beam> {
+        let state = (beam.pos.y * self.count_x + beam.pos.x) * 4 + beam.dir.clone() as usize;
+        if !trace.cycle_detector.set(state) {
+            // Seen the same position and direction again, there must be a kind of cycle.
+            return Vec::new();
+        }
+        trace.energized.set(beam.pos);
+
+        match self.fields[beam.pos.y][beam.pos.x] {
+            Field::Empty => return self.advance_beam(beam, &beam.dir),
+            Field::MirrorSlash => match beam.dir {
+                North => return self.advance_beam(beam, &East),
+                West => return self.advance_beam(beam, &South),
+                South => return self.advance_beam(beam, &West),
+                East => return self.advance_beam(beam, &North),
+            },
+            Field::MirrorBackslash => match beam.dir {
+                North => return self.advance_beam(beam, &West),
+                West => return self.advance_beam(beam, &North),
+                South => return self.advance_beam(beam, &East),
+                East => return self.advance_beam(beam, &South),
+            },
+            Field::SplitterHorizontal => match beam.dir {
+                East | West => return self.advance_beam(beam, &beam.dir),
+                North | South => return self.split_beam(beam, East, West),
+            },
+            Field::SplitterVertical => match beam.dir {
+                North | South => return self.advance_beam(beam, &beam.dir),
+                East | West => return self.split_beam(beam, North, South),
+            },
+        }
+    }
+
+    fn next_pos(&self, pos: Vec2<usize>, dir: &Direction) -> Option<Vec2<usize>> {
+        let size = Vec2::new(self.count_x, self.count_y);
+        match dir {
+            Direction::North => pos.checked_sub(Vec2::new(0, 1)),
+            Direction::South => Some(pos + Vec2::new(0, 1)).filter(|p| p.is_within(size)),
+            Direction::West => pos.checked_sub(Vec2::new(1, 0)),
+            Direction::East => Some(pos + Vec2::new(1, 0)).filter(|p| p.is_within(size)),
+        }
+    }
+
+    fn advance_beam(&self, beam: &Beam, dir: &Direction) -> Vec<Beam> {
+        if let Some(next_pos) = self.next_pos(beam.pos, &dir) {
+            vec![Beam {
+                pos: next_pos,
+                dir: dir.clone(),
+            }]
+        } else {
+            vec![]
+        }
+    }
+
+    fn split_beam(&self, beam: &Beam, dir1: Direction, dir2: Direction) -> Vec<Beam> {
+        let mut result = Vec::new();
+        if let Some(next_pos) = self.next_pos(beam.pos, &dir1) {
+            result.push(Beam {
+                pos: next_pos,
+                dir: dir1,
+            });
+        }
+        if let Some(next_pos) = self.next_pos(beam.pos, &dir2) {
+            result.push(Beam {
+                pos: next_pos,
+                dir: dir2,
+            });
+        }
+        result
+    }
+
+    fn trace_from(&self, pos: Vec2<usize>, dir: Direction) -> usize {
+        let mut beams = vec![Beam { pos, dir }];
+        let mut trace = Trace {
+            energized: BitGrid::new(self.count_x, self.count_y),
+            cycle_detector: BitSet::new(self.count_x * self.count_y * 4),
+        };
+
+        while !beams.is_empty() {
+            let old_beams: Vec<_> = std::mem::take(&mut beams);
+            for beam in old_beams {
+                let mut advanced = self.beam_step(&mut trace, &beam);
+                beams.append(&mut advanced);
+            }
+        }
+
+        trace.energized.count()
+    }
+
+    fn trace_beams_from_all_sides(&self) -> usize {
+        let mut starts = Vec::new();
+
+        for x in 0..self.count_x {
+            starts.push((Vec2::new(x, 0), South));
+            starts.push((Vec2::new(x, self.count_y - 1), North));
+        }
+        for y in 0..self.count_y {
+            starts.push((Vec2::new(0, y), East));
+            starts.push((Vec2::new(self.count_x - 1, y), West));
+        }
+
+        #[cfg(feature = "parallel")]
+        let starts = starts.into_par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let starts = starts.into_iter();
+
+        starts
+            .map(|(pos, dir)| self.trace_from(pos, dir))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    let contraption = Contraption::new(input)?;
+    return Ok(contraption.trace_from(Vec2::new(0, 0), East));
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    let contraption = Contraption::new(input)?;
+    return Ok(contraption.trace_beams_from_all_sides());
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = Args::from_env();
+    parallel::init_threads(&mut args).map_err(Error::Usage)?;
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::Stream)?;
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 46);
+        assert_eq!(part2(SAMPLE).unwrap(), 51);
+    }
+}
@@ -1,14 +1,30 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec2;
+use std::fmt::Display;
 use std::{
     collections::{BinaryHeap, HashMap},
-    num::ParseIntError,
     time::Instant,
 };
 
 #[derive(Debug)]
 enum Error {
-    ParseError(ParseIntError),
+    ParseError(ParseError),
+    StreamError(StreamError),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
 #[derive(PartialEq, Eq, Clone, Copy, Hash)]
 enum Direction {
     North,
@@ -21,13 +37,13 @@ struct HeatLossMap {
     temperatures: Vec<Vec<usize>>,
     count_x: usize,
     count_y: usize,
-    start: (usize, usize),
-    target: (usize, usize),
+    start: Vec2<usize>,
+    target: Vec2<usize>,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Hash)]
 struct Crucible {
-    pos: (usize, usize),
+    pos: Vec2<usize>,
     dir: Direction,
     dir_steps: usize,
 }
@@ -52,15 +68,8 @@ impl Ord for HeapEntry {
 
 impl HeatLossMap {
     fn new(input: &str) -> Result<HeatLossMap, Error> {
-        let maybe_temperatures: Result<Vec<Vec<usize>>, Error> = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| String::from(c).parse().map_err(|e| Error::ParseError(e)))
-                    .collect()
-            })
-            .collect();
-        let temperatures = maybe_temperatures?;
+        let digits = grid("digit", |c| c.to_digit(10).map(|d| d as usize));
+        let temperatures = parse::parse(digits, input).map_err(Error::ParseError)?;
 
         let count_x = temperatures[0].len();
         let count_y = temperatures.len();
@@ -69,8 +78,8 @@ impl HeatLossMap {
             temperatures,
             count_x,
             count_y,
-            start: (0, 0),
-            target: (count_x - 1, count_y - 1),
+            start: Vec2::new(0, 0),
+            target: Vec2::new(count_x - 1, count_y - 1),
         })
     }
 
@@ -120,7 +129,7 @@ impl HeatLossMap {
             let current_g_score = *g_score.get(&current).unwrap();
             for candidate in self.possible_moves(&current, &advance) {
                 let tentative_g_score =
-                    current_g_score + self.temperatures[candidate.pos.1][candidate.pos.0];
+                    current_g_score + self.temperatures[candidate.pos.y][candidate.pos.x];
                 if tentative_g_score < *g_score.get(&candidate).unwrap_or(&usize::MAX) {
                     g_score.insert(candidate, tentative_g_score);
                     open_set.push(HeapEntry {
@@ -135,9 +144,7 @@ impl HeatLossMap {
     }
 
     fn estimate_cost(&self, crucible: &Crucible) -> usize {
-        let from = crucible.pos;
-        let to = self.target;
-        (from.0.max(to.0) - from.0.min(to.0)) + (from.1.max(to.1) - from.1.min(to.1))
+        crucible.pos.manhattan(&self.target)
     }
 
     fn possible_moves<F>(&self, crucible: &Crucible, advance: F) -> Vec<Crucible>
@@ -218,68 +225,67 @@ impl HeatLossMap {
         }
     }
 
-    fn advance_pos(&self, pos: (usize, usize), dir: Direction) -> Option<(usize, usize)> {
+    fn advance_pos(&self, pos: Vec2<usize>, dir: Direction) -> Option<Vec2<usize>> {
+        let size = Vec2::new(self.count_x, self.count_y);
         match dir {
-            Direction::North => {
-                if pos.1 > 0 {
-                    Some((pos.0, pos.1 - 1))
-                } else {
-                    None
-                }
-            }
-            Direction::South => {
-                if (pos.1 + 1) < self.count_y {
-                    Some((pos.0, pos.1 + 1))
-                } else {
-                    None
-                }
-            }
-            Direction::West => {
-                if pos.0 > 0 {
-                    Some((pos.0 - 1, pos.1))
-                } else {
-                    None
-                }
-            }
-            Direction::East => {
-                if (pos.0 + 1) < self.count_x {
-                    Some((pos.0 + 1, pos.1))
-                } else {
-                    None
-                }
-            }
+            Direction::North => pos.checked_sub(Vec2::new(0, 1)),
+            Direction::South => Some(pos + Vec2::new(0, 1)).filter(|p| p.is_within(size)),
+            Direction::West => pos.checked_sub(Vec2::new(1, 0)),
+            Direction::East => Some(pos + Vec2::new(1, 0)).filter(|p| p.is_within(size)),
         }
     }
 }
 
-fn part1(input: &str) -> Result<(), Error> {
+fn part1(input: &str) -> Result<usize, Error> {
     let map = HeatLossMap::new(input)?;
-    println!(
-        "Part 1: {}",
-        map.a_star(HeatLossMap::advance_crucible_part1)
-    );
-    return Ok(());
+    return Ok(map.a_star(HeatLossMap::advance_crucible_part1));
 }
 
-fn part2(input: &str) -> Result<(), Error> {
+fn part2(input: &str) -> Result<usize, Error> {
     let map = HeatLossMap::new(input)?;
-    println!(
-        "Part 2: {}",
-        map.a_star(HeatLossMap::advance_crucible_part2)
-    );
-    return Ok(());
+    return Ok(map.a_star(HeatLossMap::advance_crucible_part2));
 }
 
 fn main() -> Result<(), Error> {
-    let input = include_str!("../rsc/input.txt");
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
 
     let start1 = Instant::now();
-    part1(input)?;
+    println!("Part 1: {}", part1(&input)?);
     println!("Elapsed: {:.2?}\n", start1.elapsed());
 
     let start2 = Instant::now();
-    part2(input)?;
+    println!("Part 2: {}", part2(&input)?);
     println!("Elapsed: {:.2?}", start2.elapsed());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 102);
+        assert_eq!(part2(SAMPLE).unwrap(), 94);
+    }
+}
@@ -0,0 +1,161 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{
+    self, delimited, integer, keyword, lines, pair, space1, tag, take_while1, terminated, try_map,
+    Input, ParseError, ParseResult,
+};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec2;
+use std::fmt::Display;
+use std::time::Instant;
+
+#[derive(Debug)]
+enum Error {
+    ParseError(ParseError),
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+const LEFT: Vec2<i64> = Vec2::new(-1, 0);
+const RIGHT: Vec2<i64> = Vec2::new(1, 0);
+const UP: Vec2<i64> = Vec2::new(0, -1);
+const DOWN: Vec2<i64> = Vec2::new(0, 1);
+const DIRECTIONS: [(&str, Vec2<i64>); 4] = [("L", LEFT), ("R", RIGHT), ("U", UP), ("D", DOWN)];
+
+// The color encodes the real instruction: five hex digits of distance, then the direction.
+fn decode_color(color: &str) -> Option<Vec2<i64>> {
+    if color.len() != 6 {
+        return None;
+    }
+
+    let distance = i64::from_str_radix(&color[..5], 16).ok()?;
+    let direction = match &color[5..] {
+        "0" => RIGHT,
+        "1" => DOWN,
+        "2" => LEFT,
+        "3" => UP,
+        _ => return None,
+    };
+    Some(direction * distance)
+}
+
+// How far to dig for part 1 and part 2.
+type Step = (Vec2<i64>, Vec2<i64>);
+
+// "R 6 (#70c710)"
+fn step(input: Input) -> ParseResult<Step> {
+    let direction = terminated(keyword(&DIRECTIONS), space1());
+    let distance = terminated(integer::<i64>(), space1());
+    let hex = take_while1("hex digit", |c| c.is_ascii_hexdigit());
+    let color = delimited(
+        tag("(#"),
+        try_map(hex, "six hex digits, the last one 0-3", decode_color),
+        tag(")"),
+    );
+    let (((direction, distance), color), rest) = pair(pair(direction, distance), color)(input)?;
+    Ok(((direction * distance, color), rest))
+}
+
+fn parse(input: &str) -> Result<Vec<Step>, Error> {
+    parse::parse(lines(step), input).map_err(Error::ParseError)
+}
+
+fn calc_area(nodes: &Vec<Vec2<i64>>) -> i64 {
+    let mut area = 0;
+    let mut perimeter = 0;
+    let count = nodes.len();
+    for i in 0..count {
+        let j = (i + 1) % count;
+        let n1 = nodes[i];
+        let n2 = nodes[j];
+        area += n1.cross(&n2);
+        // Edges are always horizontal or vertical.
+        perimeter += n1.manhattan(&n2);
+    }
+
+    area /= 2; // Until here, it's the Shoelace formula.
+
+    // Apply Pick's theorem to get the actual area.
+    area += (perimeter / 2) + 1;
+    area
+}
+
+fn part1(input: &str) -> Result<i64, Error> {
+    let mut start = Vec2::new(0, 0);
+    let mut nodes = Vec::new();
+    for (step, _) in parse(input)? {
+        let end = start + step;
+
+        nodes.push(end);
+        start = end;
+    }
+
+    return Ok(calc_area(&nodes));
+}
+
+fn part2(input: &str) -> Result<i64, Error> {
+    let mut start = Vec2::new(0, 0);
+    let mut nodes = Vec::new();
+    for (_, step) in parse(input)? {
+        let end = start + step;
+
+        nodes.push(end);
+        start = end;
+    }
+
+    return Ok(calc_area(&nodes));
+}
+
+fn main() -> Result<(), Error> {
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 62);
+        assert_eq!(part2(SAMPLE).unwrap(), 952408144115);
+    }
+}
@@ -0,0 +1,422 @@
+use aoc_common::anonymize::{Names, Rng};
+use aoc_common::cli::{Args, UsageError};
+use aoc_common::interval::{Interval, IntervalBox};
+use aoc_common::parse::{
+    self, delimited, either, integer, key_value, keyword, line_end, lines, map, pair, separated,
+    tag, take_while1, terminated, word, Input, ParseError, ParseResult,
+};
+use aoc_common::stream::{Source, StreamError};
+use std::fmt::Display;
+use std::{collections::HashMap, time::Instant};
+
+// TODO: Optimize; there's just four parts, can map them to integers and ditch the hash map for the parts.
+
+#[derive(Debug)]
+enum Error {
+    ParseError(ParseError),
+    UsageError(UsageError),
+    MissingWorkflow,
+    MissingPartInEvaluation,
+    NoWorkflowResult,
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::UsageError(e) => write!(f, "{}", e),
+            Error::MissingWorkflow => write!(f, "a workflow is missing"),
+            Error::MissingPartInEvaluation => {
+                write!(f, "a rule asks for a category the part doesn't have")
+            }
+            Error::NoWorkflowResult => write!(f, "a workflow ends without a result"),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Clone)]
+enum Rule {
+    LessThan {
+        part: String,
+        value: usize,
+        workflow: String,
+    },
+    GreaterThan {
+        part: String,
+        value: usize,
+        workflow: String,
+    },
+    Accept,
+    Reject,
+    Jump(String),
+}
+
+impl Rule {
+    // "a<2006:qkq", "m>2090:A", "rfg", "A" or "R"
+    fn parse(input: Input) -> ParseResult<Rule> {
+        let condition = pair(
+            pair(name(), keyword(&[("<", '<'), (">", '>')])),
+            terminated(integer(), tag(":")),
+        );
+        let conditional = map(
+            pair(condition, word()),
+            |(((part, op), value), workflow)| {
+                let part = part.to_string();
+                let workflow = workflow.to_string();
+                if op == '<' {
+                    Rule::LessThan {
+                        part,
+                        value,
+                        workflow,
+                    }
+                } else {
+                    Rule::GreaterThan {
+                        part,
+                        value,
+                        workflow,
+                    }
+                }
+            },
+        );
+        let unconditional = map(word(), |target| match target {
+            "A" => Rule::Accept,
+            "R" => Rule::Reject,
+            _ => Rule::Jump(target.to_string()),
+        });
+
+        either(conditional, unconditional)(input)
+    }
+}
+
+// Names of workflows and parts.
+fn name<'a>() -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    take_while1("name", |c| c.is_ascii_lowercase())
+}
+
+// The accepted ranges of the x, m, a and s ratings, in this order.
+type Limits = IntervalBox<usize, 4>;
+
+fn axis(name: &str) -> usize {
+    match name {
+        "x" => 0,
+        "m" => 1,
+        "a" => 2,
+        "s" => 3,
+        _ => panic!("Unknown part"),
+    }
+}
+
+struct Evaluator<'a> {
+    rules: &'a HashMap<String, Vec<Rule>>,
+    parts: HashMap<String, usize>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(rules: &'a HashMap<String, Vec<Rule>>, parts: &Vec<(String, usize)>) -> Evaluator<'a> {
+        let mut mapped: HashMap<String, usize> = HashMap::new();
+        for (name, value) in parts {
+            mapped.insert(name.clone(), *value);
+        }
+
+        Evaluator {
+            rules,
+            parts: mapped,
+        }
+    }
+
+    fn eval(&self) -> Result<bool, Error> {
+        let mut workflow_name = &"in".to_string();
+
+        'outer: while let Some(rule_list) = self.rules.get(workflow_name) {
+            for rule in rule_list {
+                match rule {
+                    Rule::Accept => return Ok(true),
+                    Rule::Reject => return Ok(false),
+                    Rule::Jump(target) => {
+                        workflow_name = target;
+                        continue 'outer;
+                    }
+                    Rule::LessThan {
+                        part,
+                        value,
+                        workflow,
+                    } => {
+                        let part_value =
+                            self.parts.get(part).ok_or(Error::MissingPartInEvaluation)?;
+                        if part_value < value {
+                            match workflow.as_str() {
+                                "A" => return Ok(true),
+                                "R" => return Ok(false),
+                                _ => (),
+                            }
+                            workflow_name = workflow;
+                            continue 'outer;
+                        }
+                    }
+                    Rule::GreaterThan {
+                        part,
+                        value,
+                        workflow,
+                    } => {
+                        let part_value =
+                            self.parts.get(part).ok_or(Error::MissingPartInEvaluation)?;
+                        if part_value > value {
+                            match workflow.as_str() {
+                                "A" => return Ok(true),
+                                "R" => return Ok(false),
+                                _ => (),
+                            }
+                            workflow_name = workflow;
+                            continue 'outer;
+                        }
+                    }
+                }
+            }
+
+            return Err(Error::NoWorkflowResult);
+        }
+
+        Err(Error::MissingWorkflow)
+    }
+
+    fn value(&self) -> usize {
+        self.parts.iter().fold(0, |acc, entry| acc + entry.1)
+    }
+
+    fn find_combinations(&self) -> usize {
+        let limits = Limits::new([Interval::new(1, 4001); 4]);
+
+        self.limit(&"in".to_string(), &limits)
+    }
+
+    fn limit(&self, workflow_or_action: &String, current: &Limits) -> usize {
+        if workflow_or_action == "A" {
+            return current.volume();
+        }
+        if workflow_or_action == "R" {
+            return 0;
+        }
+
+        let mut current = *current;
+        let mut sum = 0;
+
+        for rule in self.rules.get(workflow_or_action).unwrap() {
+            match rule {
+                Rule::Accept => {
+                    sum += current.volume();
+                    return sum;
+                }
+                Rule::Reject => (),
+                Rule::Jump(target) => {
+                    sum += self.limit(target, &current);
+                }
+                Rule::LessThan {
+                    part,
+                    value,
+                    workflow,
+                } => {
+                    let (limited, rest) = current.split_at(axis(part), *value);
+                    sum += self.limit(workflow, &limited);
+                    current = rest;
+                }
+                Rule::GreaterThan {
+                    part,
+                    value,
+                    workflow,
+                } => {
+                    let (rest, limited) = current.split_at(axis(part), value + 1);
+                    sum += self.limit(workflow, &limited);
+                    current = rest;
+                }
+            }
+        }
+
+        sum
+    }
+}
+
+// "px{a<2006:qkq,m>2090:A,rfg}"
+fn workflow(input: Input) -> ParseResult<(String, Vec<Rule>)> {
+    let rules = delimited(tag("{"), separated(Rule::parse, tag(",")), tag("}"));
+    map(pair(name(), rules), |(name, rules)| {
+        (name.to_string(), rules)
+    })(input)
+}
+
+// "{x=787,m=2655,a=1222,s=2876}"
+fn rating(input: Input) -> ParseResult<Vec<(String, usize)>> {
+    let category = map(key_value(name(), tag("="), integer()), |(name, value)| {
+        (name.to_string(), value)
+    });
+    delimited(tag("{"), separated(category, tag(",")), tag("}"))(input)
+}
+
+type Workflows = Vec<(String, Vec<Rule>)>;
+type Ratings = Vec<Vec<(String, usize)>>;
+
+// The workflows and ratings in the order of the input.
+fn parse_lines(input: &str) -> Result<(Workflows, Ratings), Error> {
+    let system = pair(
+        terminated(lines(workflow), pair(line_end(), line_end())),
+        lines(rating),
+    );
+    parse::parse(system, input).map_err(Error::ParseError)
+}
+
+fn parse(input: &str) -> Result<(HashMap<String, Vec<Rule>>, Ratings), Error> {
+    let (rules, ratings) = parse_lines(input)?;
+
+    Ok((rules.into_iter().collect(), ratings))
+}
+
+fn made_up_name(rng: &mut Rng, name: &str) -> String {
+    rng.pick("abcdefghijklmnopqrstuvwxyz", name.len().max(2))
+}
+
+// The same workflows with other names, and the workflows and ratings in another order. "in" keeps
+// its name, as do A and R.
+fn anonymize(input: &str, seed: u64) -> Result<String, Error> {
+    let (mut workflows, mut ratings) = parse_lines(input)?;
+    let mut names = Names::new(seed);
+    for name in ["in", "A", "R"] {
+        names.keep(name);
+    }
+    names.rng().shuffle(&mut workflows);
+    names.rng().shuffle(&mut ratings);
+
+    let mut anonymized = String::new();
+    for (name, rules) in workflows {
+        let mut texts = Vec::new();
+        for rule in rules {
+            texts.push(match rule {
+                Rule::LessThan {
+                    part,
+                    value,
+                    workflow,
+                } => format!(
+                    "{}<{}:{}",
+                    part,
+                    value,
+                    names.rename(&workflow, made_up_name)
+                ),
+                Rule::GreaterThan {
+                    part,
+                    value,
+                    workflow,
+                } => format!(
+                    "{}>{}:{}",
+                    part,
+                    value,
+                    names.rename(&workflow, made_up_name)
+                ),
+                Rule::Accept => "A".to_string(),
+                Rule::Reject => "R".to_string(),
+                Rule::Jump(workflow) => names.rename(&workflow, made_up_name),
+            });
+        }
+        anonymized += &format!(
+            "{}{{{}}}\n",
+            names.rename(&name, made_up_name),
+            texts.join(",")
+        );
+    }
+
+    anonymized += "\n";
+    for rating in ratings {
+        let categories: Vec<String> = rating
+            .iter()
+            .map(|(category, value)| format!("{}={}", category, value))
+            .collect();
+        anonymized += &format!("{{{}}}\n", categories.join(","));
+    }
+    Ok(anonymized)
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    let (rules, ratings) = parse(input)?;
+    let mut accepted = 0;
+
+    for rating in ratings {
+        let evaluator = Evaluator::new(&rules, &rating);
+        if evaluator.eval()? {
+            accepted += evaluator.value();
+        }
+    }
+
+    return Ok(accepted);
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    let (rules, _) = parse(input)?;
+    let evaluator = Evaluator::new(&rules, &Vec::new());
+    return Ok(evaluator.find_combinations());
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = Args::from_env();
+    let anonymize_seed: Option<u64> = args.value("--anonymize").map_err(Error::UsageError)?;
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    if let Some(seed) = anonymize_seed {
+        print!("{}", anonymize(&input, seed)?);
+        return Ok(());
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 19114);
+        assert_eq!(part2(SAMPLE).unwrap(), 167409079868000);
+    }
+
+    #[test]
+    fn anonymizing_keeps_the_answers() {
+        for seed in 0..20 {
+            let anonymized = anonymize(SAMPLE, seed).unwrap();
+            assert_eq!(part1(&anonymized).unwrap(), 19114);
+            assert_eq!(part2(&anonymized).unwrap(), 167409079868000);
+            assert_eq!(anonymized, anonymize(SAMPLE, seed).unwrap());
+            assert!(!anonymized.contains("qqz"));
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use aoc_common::cli::Args;
+use aoc_common::model::{self, Loaded};
+use aoc_common::parse::{
+    self, integer, lines, pair, preceded, separated, space1, tag, terminated, word, Input,
+    Location, ParseError, ParseResult,
+};
+use aoc_common::stream::Source;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Instant;
+
+const PUZZLE: &str = "2023/day2";
+// 2 keeps every round, 1 had the most cubes of each color.
+const MODEL_VERSION: u32 = 2;
+
+// The bag of part 1, and the colors of part 2, unless --bag or --bag-file gives another one.
+const BAG: &str = include_str!("../bag.txt");
+
+// The number of cubes of each color, in a round or in a bag. Colors can be any word.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Cubes(BTreeMap<String, u32>);
+
+impl Cubes {
+    fn get(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    // The numbers of the colors of the bag multiplied together. Colors missing here count as 0,
+    // like a game that never shows blue needs 0 blue cubes.
+    fn power(&self, bag: &Cubes) -> u32 {
+        bag.0.keys().map(|color| self.get(color)).product()
+    }
+}
+
+impl Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cubes: Vec<String> = self
+            .0
+            .iter()
+            .map(|(color, count)| format!("{} {}", count, color))
+            .collect();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+// Like a round: "12 red, 13 green, 14 blue".
+impl FromStr for Cubes {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse::parse(cubes, s)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Game {
+    id: u32,
+    rounds: Vec<Cubes>,
+}
+
+// Why a game can't be played with a bag: the first round showing more cubes of a color than the
+// bag has.
+#[derive(Debug, PartialEq, Eq)]
+struct Shortage<'a> {
+    // Counting from 1.
+    round: usize,
+    color: &'a str,
+    shown: u32,
+    in_bag: u32,
+}
+
+impl Game {
+    fn impossible_round(&self, bag: &Cubes) -> Option<Shortage<'_>> {
+        self.rounds.iter().enumerate().find_map(|(i, round)| {
+            round
+                .0
+                .iter()
+                .find(|(color, &shown)| shown > bag.get(color))
+                .map(|(color, &shown)| Shortage {
+                    round: i + 1,
+                    color,
+                    shown,
+                    in_bag: bag.get(color),
+                })
+        })
+    }
+
+    fn is_possible(&self, bag: &Cubes) -> bool {
+        self.impossible_round(bag).is_none()
+    }
+
+    // The fewest cubes of each color the game could have been played with.
+    fn minimum_bag(&self) -> Cubes {
+        let mut bag = Cubes::default();
+        for (color, &count) in self.rounds.iter().flat_map(|round| &round.0) {
+            let most = bag.0.entry(color.clone()).or_insert(0);
+            *most = (*most).max(count);
+        }
+        bag
+    }
+}
+
+// "3 blue, 4 red". A color showing up twice counts twice.
+fn cubes(input: Input) -> ParseResult<Cubes> {
+    let count = pair(terminated(integer::<u32>(), space1()), word());
+    let (counts, rest) = separated(count, tag(", "))(input)?;
+
+    let mut cubes = Cubes::default();
+    for (count, color) in counts {
+        *cubes.0.entry(color.to_string()).or_insert(0) += count;
+    }
+
+    Ok((cubes, rest))
+}
+
+// "Game 1: 3 blue, 4 red; 1 red, 2 green"
+fn game(input: Input) -> ParseResult<Game> {
+    let id = preceded(tag("Game "), terminated(integer(), tag(": ")));
+    let ((id, rounds), rest) = pair(id, separated(cubes, tag("; ")))(input)?;
+
+    Ok((Game { id, rounds }, rest))
+}
+
+fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    parse::parse(lines(game), input)
+}
+
+// Lines of cubes like "12 red, 13 green", besides comments and blank lines. All of them go into
+// the bag.
+fn parse_bag(text: &str) -> Result<Cubes, ParseError> {
+    let mut bag = Cubes::default();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let start = Location {
+            line: number + 1,
+            column: 1,
+        };
+        let cubes: Cubes = line.parse().map_err(|e: ParseError| e.relative_to(start))?;
+        for (color, count) in cubes.0 {
+            *bag.0.entry(color).or_insert(0) += count;
+        }
+    }
+
+    Ok(bag)
+}
+
+fn part1(games: &[Game], bag: &Cubes) -> Result<u32, Box<dyn Error>> {
+    let sum: u32 = games
+        .iter()
+        .filter(|g| g.is_possible(bag))
+        .fold(0, |acc, g| acc + g.id);
+
+    return Ok(sum);
+}
+
+fn part2(games: &[Game], bag: &Cubes) -> Result<u32, Box<dyn Error>> {
+    let sum: u32 = games
+        .iter()
+        .fold(0, |acc, game| acc + game.minimum_bag().power(bag));
+
+    return Ok(sum);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = Args::from_env();
+    let save_model: Option<PathBuf> = args.value("--save-model")?;
+    let bag_file: Option<PathBuf> = args.value("--bag-file")?;
+    let bag: Cubes = match args.value("--bag")? {
+        Some(bag) => bag,
+        None => match bag_file {
+            Some(path) => parse_bag(&fs::read_to_string(path)?)?,
+            None => parse_bag(BAG)?,
+        },
+    };
+    let show_impossible = args.flag("--impossible");
+    let source = Source::from_args(args, include_str!("../rsc/input.txt"))?;
+
+    let start = Instant::now();
+    let games = match model::read(&source, PUZZLE, MODEL_VERSION)? {
+        Loaded::Model(games) => games,
+        Loaded::Text(input) => parse(&input)?,
+    };
+    println!("Input: {:.2?}\n", start.elapsed());
+
+    if let Some(path) = save_model {
+        model::save(&path, PUZZLE, MODEL_VERSION, &games)?;
+        println!("Saved the model to {}", path.display());
+        return Ok(());
+    }
+
+    if show_impossible {
+        for game in &games {
+            if let Some(shortage) = game.impossible_round(&bag) {
+                println!(
+                    "Game {}: round {} shows {} {}, the bag has {}",
+                    game.id, shortage.round, shortage.shown, shortage.color, shortage.in_bag
+                );
+            }
+        }
+        return Ok(());
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&games, &bag)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&games, &bag)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        let games = parse(SAMPLE).unwrap();
+        assert_eq!(part1(&games, &parse_bag(BAG).unwrap()).unwrap(), 8);
+        assert_eq!(part2(&games, &parse_bag(BAG).unwrap()).unwrap(), 2286);
+    }
+
+    #[test]
+    fn queries() {
+        let games = parse(SAMPLE).unwrap();
+        let bag: Cubes = parse_bag(BAG).unwrap();
+        assert_eq!(
+            games[2].impossible_round(&bag),
+            Some(Shortage {
+                round: 1,
+                color: "red",
+                shown: 20,
+                in_bag: 12
+            })
+        );
+        assert_eq!(games[3].impossible_round(&bag).unwrap().round, 3);
+        assert!(games[0].is_possible(&bag));
+        assert_eq!(games[0].minimum_bag().to_string(), "6 blue, 2 green, 4 red");
+
+        let bigger: Cubes = "20 red, 13 green, 15 blue".parse().unwrap();
+        assert_eq!(part1(&games, &bigger).unwrap(), 15);
+    }
+
+    #[test]
+    fn any_color() {
+        let games = parse("Game 7: 2 teal, 1 red; 3 teal\n").unwrap();
+        assert_eq!(games[0].minimum_bag().to_string(), "1 red, 3 teal");
+        assert!(!games[0].is_possible(&parse_bag(BAG).unwrap()));
+        assert!(games[0].is_possible(&"3 teal, 1 red".parse().unwrap()));
+        assert!("12 red, blue".parse::<Cubes>().is_err());
+    }
+
+    #[test]
+    fn power_of_the_colors_of_the_bag() {
+        let bag: Cubes = parse_bag(BAG).unwrap();
+        let games =
+            parse("Game 1: 2 red, 3 green\nGame 2: 2 red, 3 green; 4 blue, 1 teal\n").unwrap();
+        // Game 1 needs no blue cubes at all, and teal isn't in the bag.
+        assert_eq!(part2(&games, &bag).unwrap(), 24);
+        let with_teal: Cubes = "1 red, 1 green, 1 blue, 1 teal".parse().unwrap();
+        assert_eq!(part2(&games, &with_teal).unwrap(), 24);
+    }
+
+    #[test]
+    fn bags_from_files() {
+        assert_eq!(
+            parse_bag(BAG).unwrap().to_string(),
+            "14 blue, 13 green, 12 red"
+        );
+        let bag = parse_bag("# Mine\n\n2 teal, 1 red\n3 red\n").unwrap();
+        assert_eq!(bag.to_string(), "4 red, 2 teal");
+
+        let error = parse_bag("1 red\n# Mine\n2 teal,\n").unwrap_err();
+        assert_eq!(error.location.line, 3);
+    }
+}
@@ -0,0 +1,528 @@
+use aoc_common::anonymize::{Names, Rng};
+use aoc_common::assumptions::{self, Assumption, Check};
+use aoc_common::checked::{Arith, Overflow};
+use aoc_common::cli::{Args, UsageError};
+use aoc_common::graph::{Graph, NodeId};
+use aoc_common::parse::{
+    self, keyword, lines, opt, pair, preceded, separated, tag, word, Input, ParseError, ParseResult,
+};
+use aoc_common::stream::{Source, StreamError};
+use std::fmt::Display;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Instant,
+};
+
+#[derive(Debug)]
+enum Error {
+    MissingModule,
+    ParseError(ParseError),
+    UsageError(UsageError),
+    AssumptionFailed,
+    StreamError(StreamError),
+    Overflow(Overflow),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingModule => write!(f, "a module is missing"),
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::UsageError(e) => write!(f, "{}", e),
+            Error::AssumptionFailed => {
+                write!(f, "the input doesn't hold the assumptions of the solution")
+            }
+            Error::StreamError(e) => write!(f, "{}", e),
+            Error::Overflow(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+const PART1: Arith = Arith::new("2023/day20", 1);
+const PART2: Arith = Arith::new("2023/day20", 2);
+
+#[derive(Clone, PartialEq, Eq)]
+enum Pulse {
+    Low,
+    High,
+}
+
+enum Module {
+    Broadcast,
+    FlipFlop { state: bool },
+    Conjunction { inputs: HashMap<NodeId, Pulse> },
+}
+
+impl Module {
+    // Returns the pulse to send to all outputs, if any.
+    fn process(&mut self, pulse: &Pulse, from: NodeId) -> Option<Pulse> {
+        match self {
+            Module::Broadcast => Some(pulse.clone()),
+            Module::FlipFlop { state } => {
+                if pulse == &Pulse::High {
+                    return None;
+                }
+
+                *state = !*state;
+                Some(if *state { Pulse::High } else { Pulse::Low })
+            }
+            Module::Conjunction { inputs } => {
+                inputs.insert(from, pulse.clone());
+
+                if inputs.values().find(|p| **p == Pulse::Low) == None {
+                    Some(Pulse::Low)
+                } else {
+                    Some(Pulse::High)
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Module::Broadcast => (),
+            Module::FlipFlop { state } => *state = false,
+            Module::Conjunction { inputs } => {
+                for (_, v) in inputs.iter_mut() {
+                    *v = Pulse::Low;
+                }
+            }
+        }
+    }
+}
+
+// The modules are indexed by the node ID of the graph.
+struct Network<'a> {
+    graph: Graph<&'a str>,
+    modules: Vec<Module>,
+}
+
+impl Network<'_> {
+    fn module_id(&self, name: &str) -> Result<NodeId, Error> {
+        self.graph.id(name).ok_or(Error::MissingModule)
+    }
+
+    fn reset(&mut self) {
+        for module in self.modules.iter_mut() {
+            module.reset();
+        }
+    }
+
+    // Pushes the button once, calling `observe` for each pulse sent (from, to, pulse).
+    fn push_button<F>(&mut self, mut observe: F) -> Result<(), Error>
+    where
+        F: FnMut(NodeId, NodeId, &Pulse),
+    {
+        let mut next_modules = VecDeque::new();
+
+        let broadcast = self.module_id("broadcaster")?;
+        next_modules.push_back((broadcast, broadcast, Pulse::Low));
+
+        while let Some((from, to, pulse)) = next_modules.pop_front() {
+            observe(from, to, &pulse);
+
+            if let Some(out_pulse) = self.modules[to].process(&pulse, from) {
+                for next in self.graph.neighbours(to) {
+                    next_modules.push_back((to, next, out_pulse.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn push_button_part1(network: &mut Network) -> Result<(usize, usize), Error> {
+    let mut signals_low = 0;
+    let mut signals_high = 0;
+
+    network.push_button(|_, _, pulse| match pulse {
+        Pulse::Low => signals_low += 1,
+        Pulse::High => signals_high += 1,
+    })?;
+
+    Ok((signals_low, signals_high))
+}
+
+fn push_button_part2(network: &mut Network, trigger_node: NodeId) -> Result<bool, Error> {
+    let mut rx_high = 0;
+
+    network.push_button(|from, _, pulse| {
+        if from == trigger_node && pulse == &Pulse::High {
+            rx_high += 1;
+        }
+    })?;
+
+    Ok(rx_high == 1)
+}
+
+// The kind and name of a module, with its targets.
+type ModuleLine<'a> = ((Option<char>, &'a str), Vec<&'a str>);
+
+// "%a -> inv, con": the optional prefix says which kind of module it is, '%' for flip-flops and '&'
+// for conjunctions.
+fn module(input: Input<'_>) -> ParseResult<'_, ModuleLine<'_>> {
+    let kind = opt(keyword(&[("%", '%'), ("&", '&')]));
+    let targets = preceded(tag(" -> "), separated(word(), tag(", ")));
+    pair(pair(kind, word()), targets)(input)
+}
+
+fn parse_lines(input: &str) -> Result<Vec<ModuleLine<'_>>, Error> {
+    parse::parse(lines(module), input).map_err(Error::ParseError)
+}
+
+fn parse(input: &str) -> Result<Network<'_>, Error> {
+    let mut graph: Graph<&str> = Graph::directed();
+    let mut modules: Vec<Module> = Vec::new();
+
+    let lines = parse_lines(input)?;
+    for ((kind, name), targets) in lines {
+        let module = match kind {
+            Some('%') => Module::FlipFlop { state: false },
+            Some(_) => Module::Conjunction {
+                inputs: HashMap::new(),
+            },
+            None => Module::Broadcast,
+        };
+
+        let from = graph.intern(name);
+        modules.resize_with(graph.len(), || Module::Broadcast);
+        modules[from] = module;
+
+        for target in targets {
+            // Targets that are never defined become connection-less broadcasters.
+            let to = graph.intern(target);
+            graph.add_edge(from, to, 1);
+        }
+        modules.resize_with(graph.len(), || Module::Broadcast);
+    }
+
+    for (id, module) in modules.iter_mut().enumerate() {
+        if let Module::Conjunction { inputs } = module {
+            for from in graph.predecessors(id) {
+                inputs.insert(from, Pulse::Low);
+            }
+        }
+    }
+
+    Ok(Network { graph, modules })
+}
+
+// Pushes the button until `node` sends a high pulse, up to `limit` times. Returns the pushes needed.
+fn pushes_until_high(
+    network: &mut Network,
+    node: NodeId,
+    limit: usize,
+) -> Result<Option<usize>, Error> {
+    for pushes in 1..=limit {
+        if push_button_part2(network, node)? {
+            return Ok(Some(pushes));
+        }
+    }
+
+    Ok(None)
+}
+
+// The single module feeding `rx`, if it's a conjunction.
+fn rx_feeder(network: &Network) -> Result<NodeId, String> {
+    let rx = network
+        .module_id("rx")
+        .map_err(|_| "there is no rx".to_string())?;
+    let feeders: Vec<NodeId> = network.graph.predecessors(rx).collect();
+    if feeders.len() != 1 {
+        return Err(format!("rx has {} inputs", feeders.len()));
+    }
+
+    match network.modules[feeders[0]] {
+        Module::Conjunction { .. } => Ok(feeders[0]),
+        _ => Err(format!(
+            "{} is no conjunction",
+            network.graph.node(feeders[0])
+        )),
+    }
+}
+
+fn check_single_conjunction(input: &str) -> Check {
+    let network = parse(input).map_err(|e| e.to_string())?;
+    rx_feeder(&network).map(|_| ())
+}
+
+fn check_four_inputs(input: &str) -> Check {
+    let network = parse(input).map_err(|e| e.to_string())?;
+    let feeder = rx_feeder(&network)?;
+    match network.graph.predecessors(feeder).count() {
+        4 => Ok(()),
+        count => Err(format!(
+            "{} has {} inputs",
+            network.graph.node(feeder),
+            count
+        )),
+    }
+}
+
+fn check_inputs_cycle(input: &str) -> Check {
+    let mut network = parse(input).map_err(|e| e.to_string())?;
+    let feeder = rx_feeder(&network)?;
+    let inputs: Vec<NodeId> = network.graph.predecessors(feeder).collect();
+
+    for node in inputs {
+        let name = network.graph.node(node).to_string();
+        network.reset();
+
+        let first = pushes_until_high(&mut network, node, 1 << 16).map_err(|e| e.to_string())?;
+        let Some(first) = first else {
+            return Err(format!("{} never sends a high pulse", name));
+        };
+        let second = pushes_until_high(&mut network, node, first).map_err(|e| e.to_string())?;
+        if second != Some(first) {
+            return Err(format!(
+                "{} sends a high pulse after {} pushes, but not again after another {}",
+                name, first, first
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+const ASSUMPTIONS: [Assumption<str>; 3] = [
+    Assumption {
+        description: "rx is fed by a single conjunction",
+        check: check_single_conjunction,
+    },
+    Assumption {
+        description: "the conjunction feeding rx has four inputs",
+        check: check_four_inputs,
+    },
+    Assumption {
+        description: "each of these inputs sends a high pulse every n-th push, first on the n-th",
+        check: check_inputs_cycle,
+    },
+];
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+fn lcm(a: usize, b: usize) -> Result<usize, Overflow> {
+    return PART2.mul(a, b / gcd(a, b));
+}
+
+fn made_up_name(rng: &mut Rng, name: &str) -> String {
+    rng.pick("abcdefghijklmnopqrstuvwxyz", name.len().max(2))
+}
+
+// The same network with other names, and the modules in another order. The order of the targets
+// of a module stays, as it's the order the pulses are sent in.
+fn anonymize(input: &str, seed: u64) -> Result<String, Error> {
+    let mut lines = parse_lines(input)?;
+    let mut names = Names::new(seed);
+    names.keep("broadcaster");
+    names.keep("rx");
+    names.rng().shuffle(&mut lines);
+
+    let mut anonymized = String::new();
+    for ((kind, name), targets) in lines {
+        let targets: Vec<String> = targets
+            .iter()
+            .map(|t| names.rename(t, made_up_name))
+            .collect();
+        anonymized += &format!(
+            "{}{} -> {}\n",
+            kind.map(String::from).unwrap_or_default(),
+            names.rename(name, made_up_name),
+            targets.join(", ")
+        );
+    }
+    Ok(anonymized)
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    let mut network = parse(input)?;
+    let mut low = 0;
+    let mut high = 0;
+
+    for _ in 0..1000 {
+        let (signals_low, signals_high) = push_button_part1(&mut network)?;
+        low += signals_low;
+        high += signals_high;
+    }
+
+    println!("Pulses: {} low, {} high", low, high);
+    return PART1.mul(low, high).map_err(Error::Overflow);
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    let mut network = parse(input)?;
+
+    // `rx` is fed by a single conjunction. Each of its inputs sends a high pulse in a cycle; once
+    // all of them do so in the same button push, `rx` receives a low pulse.
+    let rx = network.module_id("rx")?;
+    let feeder = network
+        .graph
+        .predecessors(rx)
+        .next()
+        .ok_or(Error::MissingModule)?;
+    let trigger_nodes: Vec<NodeId> = network.graph.predecessors(feeder).collect();
+    let mut cycle_lens = Vec::new();
+
+    for trigger_node in trigger_nodes {
+        network.reset();
+
+        let mut pushes: usize = 1;
+        while !push_button_part2(&mut network, trigger_node)? {
+            pushes += 1;
+        }
+
+        println!("Cycle {}: {}", network.graph.node(trigger_node), pushes);
+        cycle_lens.push(pushes);
+    }
+
+    let mut result = cycle_lens[0];
+    for i in 1..cycle_lens.len() {
+        result = lcm(result, cycle_lens[i]).map_err(Error::Overflow)?;
+    }
+    return Ok(result);
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = Args::from_env();
+    let check_assumptions = args.flag("--check-assumptions");
+    let anonymize_seed: Option<u64> = args.value("--anonymize").map_err(Error::UsageError)?;
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    if let Some(seed) = anonymize_seed {
+        print!("{}", anonymize(&input, seed)?);
+        return Ok(());
+    }
+
+    if check_assumptions {
+        if !assumptions::report(input.as_str(), &ASSUMPTIONS) {
+            return Err(Error::AssumptionFailed);
+        }
+        println!();
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE1: &str = "\
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+
+    const SAMPLE2: &str = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    // Not from the puzzle: `hub` feeds rx, its inputs send a high pulse every 2nd and 4th push.
+    const SAMPLE3: &str = "\
+broadcaster -> fa, fb
+%fa -> ta
+&ta -> hub
+%fb -> gb
+%gb -> tb
+&tb -> hub
+&hub -> rx
+";
+
+    #[test]
+    fn answers_of_the_samples() {
+        assert_eq!(part1(SAMPLE1).unwrap(), 32000000);
+        assert_eq!(part1(SAMPLE2).unwrap(), 11687500);
+        assert_eq!(part2(SAMPLE3).unwrap(), 4);
+    }
+
+    // Which of the assumptions hold for the input, in their order.
+    fn holding(input: &str) -> Vec<bool> {
+        ASSUMPTIONS
+            .iter()
+            .map(|assumption| (assumption.check)(input).is_ok())
+            .collect()
+    }
+
+    #[test]
+    fn assumptions_on_crafted_inputs() {
+        // There is no rx at all.
+        assert_eq!(holding(SAMPLE1), [false, false, false]);
+        assert_eq!(
+            check_single_conjunction(SAMPLE1),
+            Err("there is no rx".to_string())
+        );
+
+        // hub only has two inputs, which do cycle.
+        assert_eq!(holding(SAMPLE3), [true, false, true]);
+
+        let flip_flop = "broadcaster -> a\n%a -> rx\n";
+        assert_eq!(holding(flip_flop), [false, false, false]);
+        assert_eq!(
+            check_single_conjunction(flip_flop),
+            Err("a is no conjunction".to_string())
+        );
+
+        // hub has four inputs, but one of them gets no pulse and never sends a high one.
+        let silent = "\
+broadcaster -> fa
+%fa -> ta
+&ta -> hub
+%fb -> tb
+&tb -> hub
+&tc -> hub
+&td -> hub
+&hub -> rx
+";
+        assert_eq!(holding(silent), [true, true, false]);
+    }
+
+    #[test]
+    fn anonymizing_keeps_the_answers() {
+        for seed in 0..20 {
+            for (sample, answer) in [(SAMPLE1, 32000000), (SAMPLE2, 11687500)] {
+                let anonymized = anonymize(sample, seed).unwrap();
+                assert_eq!(part1(&anonymized).unwrap(), answer);
+                assert_eq!(anonymized, anonymize(sample, seed).unwrap());
+                assert_ne!(anonymized, sample);
+            }
+            assert_eq!(part2(&anonymize(SAMPLE3, seed).unwrap()).unwrap(), 4);
+        }
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn overflow_is_an_error() {
+        assert_eq!(lcm(6, 4).unwrap(), 12);
+        let error = lcm(usize::MAX, 2).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            format!("2023/day20 part 2: {} * 2 in usize overflows", usize::MAX)
+        );
+    }
+}
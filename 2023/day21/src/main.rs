@@ -0,0 +1,518 @@
+use aoc_common::assumptions::{self, Assumption, Check};
+use aoc_common::bits::BitGrid;
+use aoc_common::cli::Args;
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::solvers::{self, Solver};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec2;
+use std::fmt::Display;
+use std::{collections::VecDeque, time::Instant};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Garden,
+    Rock,
+    Start,
+}
+
+#[derive(Debug)]
+enum Error {
+    ParseError(ParseError),
+    AssumptionFailed,
+    SolversDisagree,
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::AssumptionFailed => {
+                write!(f, "the input doesn't hold the assumptions of the solution")
+            }
+            Error::SolversDisagree => write!(f, "the solvers disagree"),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+const STEPS_PART1: isize = 64;
+const STEPS_PART2: isize = 26501365;
+
+// The positions are kept on a window of the repeated maps, `reach` maps to each side of the first
+// one. Positions outside of the first map are negative or beyond its length.
+struct Map {
+    rocks: BitGrid,
+    positions: BitGrid,
+    reach: isize,
+    x_len: isize,
+    y_len: isize,
+}
+
+impl Map {
+    fn new(input: &str) -> Result<Map, Error> {
+        let tiles = grid("'.', '#' or 'S'", |c| match c {
+            '.' => Some(Tile::Garden),
+            '#' => Some(Tile::Rock),
+            'S' => Some(Tile::Start),
+            _ => None,
+        });
+        let rows = parse::parse(tiles, input).map_err(Error::ParseError)?;
+        let x_len = rows[0].len() as isize;
+        let y_len = rows.len() as isize;
+        let mut rocks = BitGrid::new(x_len as usize, y_len as usize);
+        let mut positions = BitGrid::new(x_len as usize, y_len as usize);
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                match tile {
+                    Tile::Rock => _ = rocks.set(Vec2::new(x, y)),
+                    Tile::Start => _ = positions.set(Vec2::new(x, y)),
+                    Tile::Garden => (),
+                }
+            }
+        }
+
+        return Ok(Map {
+            rocks,
+            positions,
+            reach: 0,
+            x_len,
+            y_len,
+        });
+    }
+
+    // The map with its positions on a window wide enough to walk `steps` more steps on.
+    fn widened(&self, steps: isize) -> Map {
+        let side = self.x_len.min(self.y_len);
+        let reach = self.reach + (steps + side - 1) / side;
+        let maps = (2 * reach + 1) as usize;
+        let mut positions = BitGrid::new(maps * self.x_len as usize, maps * self.y_len as usize);
+        let shift = Vec2::new(
+            (reach - self.reach) * self.x_len,
+            (reach - self.reach) * self.y_len,
+        );
+        for pos in self.positions.iter() {
+            let pos = Vec2::new(pos.x as isize, pos.y as isize) + shift;
+            positions.set(Vec2::new(pos.x as usize, pos.y as usize));
+        }
+
+        Map {
+            rocks: self.rocks.clone(),
+            positions,
+            reach,
+            x_len: self.x_len,
+            y_len: self.y_len,
+        }
+    }
+
+    // Where a position is on the window.
+    fn on_window(&self, pos: Vec2<isize>) -> Vec2<usize> {
+        let pos = pos + Vec2::new(self.reach * self.x_len, self.reach * self.y_len);
+        Vec2::new(pos.x as usize, pos.y as usize)
+    }
+
+    fn positions(&self) -> impl Iterator<Item = Vec2<isize>> + '_ {
+        let offset = Vec2::new(self.reach * self.x_len, self.reach * self.y_len);
+        self.positions
+            .iter()
+            .map(move |pos| Vec2::new(pos.x as isize, pos.y as isize) - offset)
+    }
+
+    fn is_rock(&self, pos: Vec2<isize>) -> bool {
+        let normalized_pos = Vec2::new(
+            pos.x.rem_euclid(self.x_len) as usize,
+            pos.y.rem_euclid(self.y_len) as usize,
+        );
+        self.rocks.test(normalized_pos)
+    }
+
+    // A step further from every position. The window has to be wide enough for that, see
+    // `widened`.
+    fn step<F>(&self, next_steps: F) -> Map
+    where
+        F: Fn(&Map, &mut BitGrid, &Vec2<isize>),
+    {
+        let mut new_positions = BitGrid::new(self.positions.width(), self.positions.height());
+
+        for pos in self.positions() {
+            next_steps(self, &mut new_positions, &pos);
+        }
+
+        Map {
+            rocks: self.rocks.clone(),
+            positions: new_positions,
+            reach: self.reach,
+            x_len: self.x_len,
+            y_len: self.y_len,
+        }
+    }
+
+    fn next_steps_limited(&self, positions: &mut BitGrid, from: &Vec2<isize>) {
+        let size = Vec2::new(self.x_len, self.y_len);
+        for next in from.neighbours4().filter(|n| n.is_within(size)) {
+            self.push_step(positions, next);
+        }
+    }
+
+    fn next_steps_infinite(&self, positions: &mut BitGrid, from: &Vec2<isize>) {
+        for next in from.neighbours4() {
+            self.push_step(positions, next);
+        }
+    }
+
+    // How many steps it takes to get from S to each plot, for those reachable within `max_steps`.
+    // Only the distances are of interest, not which plot is how far away.
+    fn distances(&self, max_steps: isize, infinite: bool) -> Vec<isize> {
+        let size = Vec2::new(self.x_len, self.y_len);
+        let map = self.widened(if infinite { max_steps } else { 0 });
+        let mut visited = map.positions.clone();
+        let mut distances: Vec<isize> = map.positions().map(|_| 0).collect();
+        let mut queue: VecDeque<(Vec2<isize>, isize)> =
+            map.positions().map(|pos| (pos, 0)).collect();
+
+        while let Some((pos, distance)) = queue.pop_front() {
+            if distance == max_steps {
+                continue;
+            }
+
+            for next in pos.neighbours4() {
+                if !infinite && !next.is_within(size) {
+                    continue;
+                }
+
+                if !map.is_rock(next) && visited.set(map.on_window(next)) {
+                    distances.push(distance + 1);
+                    queue.push_back((next, distance + 1));
+                }
+            }
+        }
+
+        distances
+    }
+
+    fn push_step(&self, positions: &mut BitGrid, pos: Vec2<isize>) {
+        if !self.is_rock(pos) {
+            positions.set(self.on_window(pos));
+        }
+    }
+}
+
+// The position of S, if there's just one.
+fn start(map: &Map) -> Result<Vec2<isize>, String> {
+    match map.positions().collect::<Vec<_>>()[..] {
+        [start] => Ok(start),
+        _ => Err(format!(
+            "there are {} start positions",
+            map.positions.count()
+        )),
+    }
+}
+
+fn check_square(input: &str) -> Check {
+    let map = Map::new(input).map_err(|e| e.to_string())?;
+    if map.x_len != map.y_len || map.x_len % 2 == 0 {
+        return Err(format!("the map is {}x{}", map.x_len, map.y_len));
+    }
+
+    Ok(())
+}
+
+fn check_start_centered(input: &str) -> Check {
+    let map = Map::new(input).map_err(|e| e.to_string())?;
+    let start = start(&map)?;
+    if start != Vec2::new(map.x_len / 2, map.y_len / 2) {
+        return Err(format!(
+            "S is at {} on a {}x{} map",
+            start, map.x_len, map.y_len
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_start_lines_clear(input: &str) -> Check {
+    let map = Map::new(input).map_err(|e| e.to_string())?;
+    let start = start(&map)?;
+    let on_start_lines = |r: &Vec2<usize>| r.x as isize == start.x || r.y as isize == start.y;
+    if let Some(rock) = map.rocks.iter().find(on_start_lines) {
+        return Err(format!("there is a rock at {}", rock));
+    }
+
+    Ok(())
+}
+
+fn check_steps_reach_edges(input: &str) -> Check {
+    let map = Map::new(input).map_err(|e| e.to_string())?;
+    if STEPS_PART2 % map.x_len != map.x_len / 2 {
+        return Err(format!(
+            "{} steps leave {} over on a map {} wide",
+            STEPS_PART2,
+            STEPS_PART2 % map.x_len,
+            map.x_len
+        ));
+    }
+
+    Ok(())
+}
+
+// Part 2 fits a quadratic through the counts after walking to the edge of the map and one and two
+// maps further. That only works if the walks fill the repeated maps evenly, from a start in the
+// middle.
+const ASSUMPTIONS: [Assumption<str>; 4] = [
+    Assumption {
+        description: "the map is square, with an odd side length",
+        check: check_square,
+    },
+    Assumption {
+        description: "S is in the center of the map",
+        check: check_start_centered,
+    },
+    Assumption {
+        description: "the row and column of S are free of rocks",
+        check: check_start_lines_clear,
+    },
+    Assumption {
+        description: "the steps of part 2 end at the edge of a repeated map",
+        check: check_steps_reach_edges,
+    },
+];
+
+// A plot can be reached in exactly `steps` steps if it's at most that far away, with the same parity:
+// the remaining steps are spent going back and forth.
+fn count_reachable(distances: &[isize], steps: isize) -> isize {
+    distances
+        .iter()
+        .filter(|&&d| d <= steps && d % 2 == steps % 2)
+        .count() as isize
+}
+
+fn interpolate(values: Vec<(isize, isize)>, xi: isize) -> isize {
+    let mut result: f64 = 0.0;
+
+    for i in 0..values.len() {
+        let mut term = values[i].1 as f64;
+        for j in 0..values.len() {
+            if i == j {
+                continue;
+            }
+
+            term *= (xi - values[j].0) as f64 / (values[i].0 - values[j].0) as f64;
+        }
+        result += term
+    }
+
+    result as isize
+}
+
+fn reachable_by_stepping(input: &str) -> Result<isize, Error> {
+    let mut map = Map::new(input)?;
+    for _ in 0..STEPS_PART1 {
+        map = map.step(Map::next_steps_limited);
+    }
+    return Ok(map.positions.count() as isize);
+}
+
+fn reachable_by_distance(input: &str) -> Result<isize, Error> {
+    let map = Map::new(input)?;
+    let distances = map.distances(STEPS_PART1, false);
+    return Ok(count_reachable(&distances, STEPS_PART1));
+}
+
+// The walks to the edge of the map and one and two maps further, fitted to a quadratic.
+fn sample_points(map: &Map) -> [isize; 3] {
+    let x1 = map.x_len / 2;
+    [x1, x1 + map.x_len, x1 + 2 * map.x_len]
+}
+
+fn reachable_infinite_by_stepping(input: &str) -> Result<isize, Error> {
+    let map = Map::new(input)?;
+    let [x1, x2, x3] = sample_points(&map);
+    let mut map = map.widened(x3);
+    let mut y1 = 0;
+    let mut y2 = 0;
+    let mut y3 = 0;
+
+    for i in 1..=x3 {
+        map = map.step(Map::next_steps_infinite);
+        if i == x1 {
+            y1 = map.positions.count() as isize;
+        } else if i == x2 {
+            y2 = map.positions.count() as isize;
+        } else if i == x3 {
+            y3 = map.positions.count() as isize;
+        }
+    }
+
+    let values = vec![(x1, y1), (x2, y2), (x3, y3)];
+    return Ok(interpolate(values, STEPS_PART2));
+}
+
+// A single search finds the counts for all three walks, instead of stepping every position.
+fn reachable_infinite_by_distance(input: &str) -> Result<isize, Error> {
+    let map = Map::new(input)?;
+    let xs = sample_points(&map);
+    let distances = map.distances(xs[2], true);
+
+    let values = xs
+        .iter()
+        .map(|&x| (x, count_reachable(&distances, x)))
+        .collect();
+    return Ok(interpolate(values, STEPS_PART2));
+}
+
+const SOLVERS: [Solver<str, isize, Error>; 4] = [
+    Solver {
+        part: 1,
+        name: "distances",
+        solve: reachable_by_distance,
+    },
+    Solver {
+        part: 1,
+        name: "stepping",
+        solve: reachable_by_stepping,
+    },
+    Solver {
+        part: 2,
+        name: "distances",
+        solve: reachable_infinite_by_distance,
+    },
+    Solver {
+        part: 2,
+        name: "stepping",
+        solve: reachable_infinite_by_stepping,
+    },
+];
+
+fn part1(input: &str) -> Result<isize, Error> {
+    return reachable_by_distance(input);
+}
+
+fn part2(input: &str) -> Result<isize, Error> {
+    return reachable_infinite_by_distance(input);
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = Args::from_env();
+    let check_assumptions = args.flag("--check-assumptions");
+    let cross_check = args.flag("--cross-check");
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    if check_assumptions {
+        if !assumptions::report(input.as_str(), &ASSUMPTIONS) {
+            return Err(Error::AssumptionFailed);
+        }
+        println!();
+    }
+
+    if cross_check {
+        if !solvers::cross_check(input.as_str(), &SOLVERS)? {
+            return Err(Error::SolversDisagree);
+        }
+        return Ok(());
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 42);
+        assert_eq!(part2(SAMPLE).unwrap(), 528192700299084);
+    }
+
+    #[test]
+    fn the_solvers_agree_on_the_sample() {
+        for solver in &SOLVERS {
+            let expected = if solver.part == 1 {
+                42
+            } else {
+                528192700299084
+            };
+            let answer = (solver.solve)(SAMPLE).unwrap();
+            assert_eq!(answer, expected, "part {} ({})", solver.part, solver.name);
+        }
+        assert!(solvers::cross_check(SAMPLE, &SOLVERS).unwrap());
+    }
+
+    // Which of the assumptions hold for the input, in their order.
+    fn holding(input: &str) -> Vec<bool> {
+        ASSUMPTIONS
+            .iter()
+            .map(|assumption| (assumption.check)(input).is_ok())
+            .collect()
+    }
+
+    // An empty square map `side` wide with S in the middle.
+    fn open_map(side: usize) -> String {
+        let row = ".".repeat(side);
+        let mut rows = vec![row.clone(); side];
+        rows[side / 2].replace_range(side / 2..side / 2 + 1, "S");
+        rows.iter().map(|row| format!("{}\n", row)).collect()
+    }
+
+    #[test]
+    fn assumptions_on_crafted_inputs() {
+        // The row of S has rocks, and 11 divides the steps of part 2.
+        assert_eq!(holding(SAMPLE), [true, true, false, false]);
+
+        assert_eq!(holding(&open_map(131)), [true, true, true, true]);
+        assert_eq!(holding(&open_map(5)), [true, true, true, false]);
+        assert_eq!(
+            holding("....\n.S..\n....\n....\n"),
+            [false, false, true, false]
+        );
+        // The steps of part 2 leave 1 over on a map 3 wide, wherever S is.
+        assert_eq!(holding("...\n...\nS..\n"), [true, false, true, true]);
+        assert_eq!(
+            check_start_centered("S..\n...\n..S\n"),
+            Err("there are 2 start positions".to_string())
+        );
+    }
+
+    #[test]
+    fn the_window_covers_the_walk() {
+        let map = Map::new(SAMPLE).unwrap();
+        let [_, _, x3] = sample_points(&map);
+        let widened = map.widened(x3);
+        let (width, height) = (widened.positions.width(), widened.positions.height());
+        for start in widened.positions() {
+            // The farthest a walk of `x3` steps gets in each direction. A position left of or
+            // above the window would wrap around to a huge one.
+            for offset in [(-x3, 0), (x3, 0), (0, -x3), (0, x3)] {
+                let pos = widened.on_window(start + Vec2::new(offset.0, offset.1));
+                assert!(pos.x < width && pos.y < height, "{} is off the window", pos);
+            }
+        }
+    }
+}
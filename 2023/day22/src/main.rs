@@ -1,3 +1,13 @@
+use aoc_common::cli::{Args, UsageError};
+use aoc_common::parallel;
+use aoc_common::parse::{
+    self, integer, lines, map, pair, tag, terminated, Input, ParseError, ParseResult,
+};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec3;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt::Display;
 use std::{
     collections::{HashSet, VecDeque},
     time::Instant,
@@ -5,73 +15,69 @@ use std::{
 
 #[derive(Debug)]
 enum Error {
-    ParseError,
+    Parse(ParseError),
+    Usage(UsageError),
+    Stream(StreamError),
 }
 
-#[derive(PartialEq, Clone)]
-struct Coord {
-    x: usize,
-    y: usize,
-    z: usize,
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Usage(e) => write!(f, "{}", e),
+            Error::Stream(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// "1,0,1"
+fn coord(input: Input) -> ParseResult<Vec3<usize>> {
+    let (x, rest) = terminated(integer(), tag(","))(input)?;
+    let (y, rest) = terminated(integer(), tag(","))(rest)?;
+    let (z, rest) = integer()(rest)?;
+    Ok((Vec3::new(x, y, z), rest))
 }
 
 #[derive(PartialEq, Clone)]
 struct Brick {
     id: usize,
-    start: Coord,
-    end: Coord,
-}
-
-impl Coord {
-    fn new(input: &str) -> Result<Coord, Error> {
-        let mut parts = input.split(",");
-        let x: usize = parts
-            .next()
-            .ok_or(Error::ParseError)?
-            .parse()
-            .map_err(|_| Error::ParseError)?;
-        let y: usize = parts
-            .next()
-            .ok_or(Error::ParseError)?
-            .parse()
-            .map_err(|_| Error::ParseError)?;
-        let z: usize = parts
-            .next()
-            .ok_or(Error::ParseError)?
-            .parse()
-            .map_err(|_| Error::ParseError)?;
-        Ok(Coord { x, y, z })
-    }
+    start: Vec3<usize>,
+    end: Vec3<usize>,
 }
 
 impl Brick {
-    fn new(line: &str) -> Result<Brick, Error> {
+    fn new(c1: Vec3<usize>, c2: Vec3<usize>) -> Brick {
         static mut COUNTER: usize = 0;
 
-        let mut parts = line.split("~");
-        let c1 = Coord::new(parts.next().ok_or(Error::ParseError)?)?;
-        let c2 = Coord::new(parts.next().ok_or(Error::ParseError)?)?;
-
         let id = unsafe {
             COUNTER += 1;
             COUNTER
         };
 
         if c1.y <= c2.y {
-            Ok(Brick {
+            Brick {
                 id,
                 start: c1,
                 end: c2,
-            })
+            }
         } else {
-            Ok(Brick {
+            Brick {
                 id,
                 start: c2,
                 end: c1,
-            })
+            }
         }
     }
 
+    // "1,0,1~1,2,1"
+    fn parse(input: Input) -> ParseResult<Brick> {
+        map(pair(terminated(coord, tag("~")), coord), |(c1, c2)| {
+            Brick::new(c1, c2)
+        })(input)
+    }
+
     fn lies_on(&self, other: &Brick) -> bool {
         if self.start.z != other.end.z + 1 {
             return false;
@@ -89,8 +95,9 @@ impl Brick {
     }
 
     fn move_down(&mut self) {
-        self.start.z -= 1;
-        self.end.z -= 1;
+        let down = Vec3::new(0, 0, 1);
+        self.start -= down;
+        self.end -= down;
     }
 }
 
@@ -185,56 +192,72 @@ fn can_disintegrate(bricks: &Vec<Brick>, brick: &Brick) -> bool {
 }
 
 fn count_chainreactions(bricks: &Vec<Brick>) -> usize {
-    let mut total = 0;
-
-    for i in 0..bricks.len() {
-        let mut temp = bricks.clone();
-        temp.remove(i);
-
-        total += settle(&mut temp);
-    }
-
-    total
+    #[cfg(feature = "parallel")]
+    let indices = (0..bricks.len()).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let indices = 0..bricks.len();
+
+    indices
+        .map(|i| {
+            let mut temp = bricks.clone();
+            temp.remove(i);
+
+            settle(&mut temp)
+        })
+        .sum()
 }
 
-fn part1(input: &str) -> Result<(), Error> {
-    let mut bricks = Vec::new();
-
-    for line in input.lines() {
-        let brick = Brick::new(line)?;
-        bricks.push(brick);
-    }
+fn part1(input: &str) -> Result<usize, Error> {
+    let mut bricks = parse::parse(lines(Brick::parse), input).map_err(Error::Parse)?;
 
     settle(&mut bricks);
 
-    println!("Part 1: {}", count_disintegratable(&bricks));
-    return Ok(());
+    return Ok(count_disintegratable(&bricks));
 }
 
-fn part2(input: &str) -> Result<(), Error> {
-    let mut bricks = Vec::new();
-
-    for line in input.lines() {
-        let brick = Brick::new(line)?;
-        bricks.push(brick);
-    }
+fn part2(input: &str) -> Result<usize, Error> {
+    let mut bricks = parse::parse(lines(Brick::parse), input).map_err(Error::Parse)?;
 
     settle(&mut bricks);
 
-    println!("Part 2: {}", count_chainreactions(&bricks));
-    return Ok(());
+    return Ok(count_chainreactions(&bricks));
 }
 
 fn main() -> Result<(), Error> {
-    let input = include_str!("../rsc/input.txt");
+    let mut args = Args::from_env();
+    parallel::init_threads(&mut args).map_err(Error::Usage)?;
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::Stream)?;
 
     let start1 = Instant::now();
-    part1(input)?;
+    println!("Part 1: {}", part1(&input)?);
     println!("Elapsed: {:.2?}\n", start1.elapsed());
 
     let start2 = Instant::now();
-    part2(input)?;
+    println!("Part 2: {}", part2(&input)?);
     println!("Elapsed: {:.2?}", start2.elapsed());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 5);
+        assert_eq!(part2(SAMPLE).unwrap(), 7);
+    }
+}
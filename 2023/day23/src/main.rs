@@ -0,0 +1,444 @@
+use aoc_common::bits::{BitGrid, BitSet};
+use aoc_common::cli::Args;
+use aoc_common::graph::{Graph, NodeId};
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::solvers::{self, Solver};
+use aoc_common::stream::{Source, StreamError};
+use aoc_common::vector::Vec2;
+use std::fmt::Display;
+use std::time::Instant;
+
+#[derive(Debug)]
+enum Error {
+    InvalidMap,
+    NoPathFound,
+    SolversDisagree,
+    ParseError(ParseError),
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidMap => write!(f, "the map has no start or end"),
+            Error::NoPathFound => write!(f, "there's no path"),
+            Error::SolversDisagree => write!(f, "the solvers disagree"),
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(PartialEq, Eq)]
+enum Field {
+    Wall,
+    Empty,
+    SlopeNorth,
+    SlopeWest,
+    SlopeSouth,
+    SlopeEast,
+}
+
+#[derive(PartialEq, Eq)]
+enum Direction {
+    North,
+    West,
+    South,
+    East,
+}
+
+struct Map {
+    fields: Vec<Field>,
+    x_len: usize,
+    y_len: usize,
+    start: Vec2<usize>,
+    target: Vec2<usize>,
+}
+
+struct PathFinder {
+    graph: Graph<Vec2<usize>>,
+    start: NodeId,
+    target: NodeId,
+}
+
+impl Map {
+    fn new(input: &str) -> Result<Map, Error> {
+        let trails = grid("'#', '.', '^', '<', 'v' or '>'", |c| match c {
+            '#' => Some(Field::Wall),
+            '.' => Some(Field::Empty),
+            '^' => Some(Field::SlopeNorth),
+            '<' => Some(Field::SlopeWest),
+            'v' => Some(Field::SlopeSouth),
+            '>' => Some(Field::SlopeEast),
+            _ => None,
+        });
+        let rows = parse::parse(trails, input).map_err(Error::ParseError)?;
+        let x_len = rows[0].len();
+        let y_len = rows.len();
+        let fields: Vec<Field> = rows.into_iter().flatten().collect();
+
+        let start = Vec2::new(1, 0);
+        let target = Vec2::new(x_len - 2, y_len - 1);
+        if fields[(start.y * x_len) + start.x] != Field::Empty {
+            return Err(Error::InvalidMap);
+        }
+        if fields[(target.y * x_len) + target.x] != Field::Empty {
+            return Err(Error::InvalidMap);
+        }
+
+        Ok(Map {
+            fields,
+            x_len,
+            y_len,
+            start,
+            target,
+        })
+    }
+
+    #[inline]
+    fn pos(&self, x: usize, y: usize) -> usize {
+        (y * self.x_len) + x
+    }
+
+    fn field(&self, pos: Vec2<usize>) -> &Field {
+        &self.fields[self.pos(pos.x, pos.y)]
+    }
+
+    fn dump(&self, visited: &BitGrid) {
+        for y in 0..self.y_len {
+            for x in 0..self.x_len {
+                if visited.test(Vec2::new(x, y)) {
+                    print!("O");
+                } else {
+                    match self.field(Vec2::new(x, y)) {
+                        Field::Wall => print!("#"),
+                        Field::Empty => print!("."),
+                        Field::SlopeNorth => print!("^"),
+                        Field::SlopeWest => print!("<"),
+                        Field::SlopeSouth => print!("v"),
+                        Field::SlopeEast => print!(">"),
+                    }
+                }
+            }
+            println!();
+        }
+        println!();
+    }
+}
+
+impl PathFinder {
+    // Turns the map into a graph: each non-wall field is a node, with an edge to each field that
+    // can be moved to from there.
+    fn new(map: Map, slopes_are_slippery: bool) -> PathFinder {
+        let mut graph = Graph::directed();
+
+        for y in 0..map.y_len {
+            for x in 0..map.x_len {
+                let pos = Vec2::new(x, y);
+                if map.field(pos) == &Field::Wall {
+                    continue;
+                }
+
+                let from = graph.intern(pos);
+                for dir in [
+                    Direction::North,
+                    Direction::West,
+                    Direction::South,
+                    Direction::East,
+                ] {
+                    if let Some(to) = Self::can_move(&map, pos, dir, slopes_are_slippery) {
+                        let to = graph.intern(to);
+                        graph.add_edge(from, to, 1);
+                    }
+                }
+            }
+        }
+
+        let start = graph.intern(map.start);
+        let target = graph.intern(map.target);
+        PathFinder {
+            graph,
+            start,
+            target,
+        }
+    }
+
+    // Junctions are the fields where a path can branch (or meet another one).
+    fn is_junction(&self, id: NodeId) -> bool {
+        let mut adjacent: Vec<NodeId> = self
+            .graph
+            .neighbours(id)
+            .chain(self.graph.predecessors(id))
+            .collect();
+        adjacent.sort();
+        adjacent.dedup();
+        id == self.start || id == self.target || adjacent.len() > 2
+    }
+
+    // Contracts every corridor between two junctions (or start and target) into a single edge,
+    // weighted with its length. That leaves just a few dozen nodes.
+    fn compress(&self) -> PathFinder {
+        let mut graph = Graph::directed();
+
+        for from in (0..self.graph.len()).filter(|id| self.is_junction(*id)) {
+            let compressed_from = graph.intern(*self.graph.node(from));
+
+            for first in self.graph.neighbours(from) {
+                let mut previous = from;
+                let mut current = first;
+                let mut length = 1;
+
+                while !self.is_junction(current) {
+                    let Some(next) = self.graph.neighbours(current).find(|n| *n != previous) else {
+                        break;
+                    };
+                    previous = current;
+                    current = next;
+                    length += 1;
+                }
+
+                // Otherwise, the corridor is a dead end.
+                if self.is_junction(current) {
+                    let compressed_to = graph.intern(*self.graph.node(current));
+                    graph.add_edge(compressed_from, compressed_to, length);
+                }
+            }
+        }
+
+        let start = graph.intern(*self.graph.node(self.start));
+        let target = graph.intern(*self.graph.node(self.target));
+        PathFinder {
+            graph,
+            start,
+            target,
+        }
+    }
+
+    // Tries every path through the (compressed) graph, keeping the longest.
+    fn longest_from(&self, from: NodeId, visited: &mut BitSet) -> Option<usize> {
+        if from == self.target {
+            return Some(0);
+        }
+
+        visited.set(from);
+        let mut longest = None;
+        for edge in self.graph.edges(from) {
+            if !visited.test(edge.to) {
+                if let Some(length) = self.longest_from(edge.to, visited) {
+                    longest = longest.max(Some(length + edge.weight));
+                }
+            }
+        }
+        visited.clear(from);
+
+        longest
+    }
+
+    fn find_longest_compressed(&self) -> Option<usize> {
+        let compressed = self.compress();
+        let mut visited = BitSet::new(compressed.graph.len());
+        compressed.longest_from(compressed.start, &mut visited)
+    }
+
+    fn find_longest(&self) -> Option<usize> {
+        let mut visited = BitSet::new(self.graph.len());
+        self.walk(self.start, &mut visited)
+    }
+
+    // Didn't find a clever solution for part 2 at first. Brute force it. Took 41min on my machine.
+    fn walk(&self, from: NodeId, visited: &mut BitSet) -> Option<usize> {
+        // A fork can lead right to the target.
+        if from == self.target {
+            return Some(visited.count());
+        }
+
+        let mut current = from;
+        loop {
+            let movements = self.possible_movements(current, visited);
+            match movements.len() {
+                0 => return None, // Dead end
+                1 => {
+                    current = movements[0];
+                    visited.set(current);
+
+                    if current == self.target {
+                        return Some(visited.count());
+                    }
+                }
+                _ => {
+                    let mut max_dist = 0;
+                    for next in movements {
+                        let mut forked_visited = visited.clone();
+                        forked_visited.set(next);
+
+                        if let Some(path_length) = self.walk(next, &mut forked_visited) {
+                            max_dist = max_dist.max(path_length);
+                        }
+                    }
+                    if max_dist == 0 {
+                        return None;
+                    } else {
+                        return Some(max_dist);
+                    }
+                }
+            }
+        }
+    }
+
+    fn possible_movements(&self, from: NodeId, visited: &BitSet) -> Vec<NodeId> {
+        self.graph
+            .neighbours(from)
+            .filter(|to| !visited.test(*to))
+            .collect()
+    }
+
+    fn can_move(
+        map: &Map,
+        from: Vec2<usize>,
+        dir: Direction,
+        slopes_are_slippery: bool,
+    ) -> Option<Vec2<usize>> {
+        let size = Vec2::new(map.x_len, map.y_len);
+        let to = match dir {
+            Direction::North => from.checked_sub(Vec2::new(0, 1))?,
+            Direction::West => from.checked_sub(Vec2::new(1, 0))?,
+            Direction::South => from + Vec2::new(0, 1),
+            Direction::East => from + Vec2::new(1, 0),
+        };
+        if !to.is_within(size) {
+            return None;
+        }
+
+        if slopes_are_slippery {
+            match (map.field(to), dir) {
+                (Field::Wall, _) => return None,
+                (Field::Empty, _) => (),
+                (Field::SlopeNorth, Direction::North) => (),
+                (Field::SlopeWest, Direction::West) => (),
+                (Field::SlopeSouth, Direction::South) => (),
+                (Field::SlopeEast, Direction::East) => (),
+                _ => return None,
+            }
+        } else if map.field(to) == &Field::Wall {
+            return None;
+        }
+
+        Some(to)
+    }
+}
+
+fn longest_hike(input: &str, slopes_are_slippery: bool, compressed: bool) -> Result<usize, Error> {
+    let map = Map::new(input)?;
+    let path_finder = PathFinder::new(map, slopes_are_slippery);
+    let max_distance = if compressed {
+        path_finder.find_longest_compressed()
+    } else {
+        path_finder.find_longest()
+    };
+    max_distance.ok_or(Error::NoPathFound)
+}
+
+const SOLVERS: [Solver<str, usize, Error>; 4] = [
+    Solver {
+        part: 1,
+        name: "compressed",
+        solve: |input| longest_hike(input, true, true),
+    },
+    Solver {
+        part: 1,
+        name: "brute force",
+        solve: |input| longest_hike(input, true, false),
+    },
+    Solver {
+        part: 2,
+        name: "compressed",
+        solve: |input| longest_hike(input, false, true),
+    },
+    Solver {
+        part: 2,
+        name: "brute force",
+        solve: |input| longest_hike(input, false, false),
+    },
+];
+
+fn part1(input: &str) -> Result<usize, Error> {
+    return longest_hike(input, true, true);
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    return longest_hike(input, false, true);
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = Args::from_env();
+    let cross_check = args.flag("--cross-check");
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    if cross_check {
+        if !solvers::cross_check(input.as_str(), &SOLVERS)? {
+            return Err(Error::SolversDisagree);
+        }
+        return Ok(());
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 94);
+        assert_eq!(part2(SAMPLE).unwrap(), 154);
+    }
+
+    #[test]
+    fn the_solvers_agree_on_the_sample() {
+        for solver in &SOLVERS {
+            let expected = if solver.part == 1 { 94 } else { 154 };
+            let answer = (solver.solve)(SAMPLE).unwrap();
+            assert_eq!(answer, expected, "part {} ({})", solver.part, solver.name);
+        }
+        assert!(solvers::cross_check(SAMPLE, &SOLVERS).unwrap());
+    }
+}
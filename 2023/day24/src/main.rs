@@ -1,81 +1,53 @@
+use aoc_common::parse::{
+    delimited, integer, pair, space0, tag, terminated, Input, ParseError, ParseResult,
+};
+use aoc_common::stream::{self, Options, StreamError};
+use aoc_common::vector::Vec3;
 use rug::Float;
+use std::fmt::Display;
+use std::io::BufRead;
 use std::time::Instant;
 
 #[derive(Debug)]
 enum Error {
-    InvalidInput,
+    ParseError(ParseError),
+    StoneNotFound,
+    StreamError(StreamError),
 }
 
-#[derive(Clone, PartialEq)]
-struct Coord3 {
-    x: f64,
-    y: f64,
-    z: f64,
-}
-
-struct Hailstone {
-    pos: Coord3,
-    vel: Coord3,
-}
-
-impl Coord3 {
-    fn new(input: &str) -> Result<Coord3, Error> {
-        let mut parts = input.split(",").map(|s| s.trim());
-        let x = parts
-            .next()
-            .ok_or(Error::InvalidInput)?
-            .parse()
-            .map_err(|_| Error::InvalidInput)?;
-        let y = parts
-            .next()
-            .ok_or(Error::InvalidInput)?
-            .parse()
-            .map_err(|_| Error::InvalidInput)?;
-        let z = parts
-            .next()
-            .ok_or(Error::InvalidInput)?
-            .parse()
-            .map_err(|_| Error::InvalidInput)?;
-        Ok(Coord3 { x, y, z })
-    }
-}
-
-impl std::ops::Add for &Coord3 {
-    type Output = Coord3;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Coord3 {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-            z: self.z + rhs.z,
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StoneNotFound => write!(f, "no stone hits all hailstones"),
+            Error::StreamError(e) => write!(f, "{}", e),
         }
     }
 }
 
-impl std::ops::Sub for &Coord3 {
-    type Output = Coord3;
+impl std::error::Error for Error {}
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Coord3 {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-            z: self.z - rhs.z,
-        }
-    }
+fn coord(input: Input) -> ParseResult<Vec3<f64>> {
+    let separator = || pair(tag(","), space0());
+    let (x, rest) = terminated(integer(), separator())(input)?;
+    let (y, rest) = terminated(integer(), separator())(rest)?;
+    let (z, rest) = integer()(rest)?;
+    Ok((Vec3::new(x, y, z), rest))
 }
 
-impl std::fmt::Display for Coord3 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}, {}, {}", self.x, self.y, self.z)
-    }
+struct Hailstone {
+    pos: Vec3<f64>,
+    vel: Vec3<f64>,
 }
 
 impl Hailstone {
-    fn new(line: &str) -> Result<Hailstone, Error> {
-        let mut parts = line.split("@");
-        let pos = Coord3::new(parts.next().ok_or(Error::InvalidInput)?)?;
-        let vel = Coord3::new(parts.next().ok_or(Error::InvalidInput)?)?;
-        Ok(Hailstone { pos, vel })
+    // "19, 13, 30 @ -2,  1, -2"
+    fn parse(input: Input) -> ParseResult<Hailstone> {
+        let ((pos, vel), rest) = pair(
+            terminated(coord, delimited(space0(), tag("@"), space0())),
+            coord,
+        )(input)?;
+        Ok((Hailstone { pos, vel }, rest))
     }
 
     fn _intersection(
@@ -115,17 +87,17 @@ impl Hailstone {
 
     fn intersection_xy(&self, other: &Hailstone) -> Option<(f64, f64)> {
         let p1 = &self.pos;
-        let p2 = &self.pos + &self.vel;
+        let p2 = self.pos + self.vel;
         let p3 = &other.pos;
-        let p4 = &other.pos + &other.vel;
+        let p4 = other.pos + other.vel;
         Self::_intersection((p1.x, p1.y), (p2.x, p2.y), (p3.x, p3.y), (p4.x, p4.y))
     }
 
     fn intersection_xz(&self, other: &Hailstone) -> Option<(f64, f64)> {
         let p1 = &self.pos;
-        let p2 = &self.pos + &self.vel;
+        let p2 = self.pos + self.vel;
         let p3 = &other.pos;
-        let p4 = &other.pos + &other.vel;
+        let p4 = other.pos + other.vel;
         Self::_intersection((p1.x, p1.z), (p2.x, p2.z), (p3.x, p3.z), (p4.x, p4.z))
     }
 
@@ -134,14 +106,26 @@ impl Hailstone {
         delta_x / self.vel.x
     }
 
-    fn change_velocity(&self, vel: &Coord3) -> Hailstone {
+    fn change_velocity(&self, vel: &Vec3<f64>) -> Hailstone {
         Hailstone {
-            pos: self.pos.clone(),
-            vel: &self.vel - vel,
+            pos: self.pos,
+            vel: self.vel - *vel,
         }
     }
 }
 
+// All pairs of hailstones are looked at, so they are kept. Only the text they are read from isn't.
+fn read_hailstones(input: impl BufRead) -> Result<Vec<Hailstone>, Error> {
+    let mut hailstones = Vec::new();
+    for record in stream::lines(input) {
+        let record = record.map_err(Error::StreamError)?;
+        let hailstone = record.parse(Hailstone::parse).map_err(Error::ParseError)?;
+        hailstones.push(hailstone);
+    }
+
+    Ok(hailstones)
+}
+
 fn all_intersect_xy(hailstones: &Vec<Hailstone>) -> Option<(f64, f64)> {
     let empty: (f64, f64) = (0.0, 0.0);
     let mut intersection = empty.clone();
@@ -198,11 +182,7 @@ fn search_stone_parameters(
 ) -> Option<Hailstone> {
     for x in search_range.start..=search_range.end {
         for y in search_range.start..=search_range.end {
-            let mut vel = Coord3 {
-                x: x as f64,
-                y: y as f64,
-                z: 0.0,
-            };
+            let mut vel = Vec3::new(x as f64, y as f64, 0.0);
             let transposed: Vec<_> = hailstones.iter().map(|h| h.change_velocity(&vel)).collect();
             if let Some(xy) = all_intersect_xy(&transposed) {
                 println!("Have first intersection at {}, {}", xy.0, xy.1);
@@ -212,11 +192,7 @@ fn search_stone_parameters(
                         hailstones.iter().map(|h| h.change_velocity(&vel)).collect();
                     if let Some(xz) = all_intersect_xz(&transposed) {
                         return Some(Hailstone {
-                            pos: Coord3 {
-                                x: xy.0,
-                                y: xy.1,
-                                z: xz.1,
-                            },
+                            pos: Vec3::new(xy.0, xy.1, xz.1),
                             vel,
                         });
                     }
@@ -228,10 +204,8 @@ fn search_stone_parameters(
     None
 }
 
-fn part1(input: &str) -> Result<(), Error> {
-    let maybe_hailstones: Result<Vec<Hailstone>, Error> =
-        input.lines().map(|line| Hailstone::new(line)).collect();
-    let hailstones = maybe_hailstones?;
+fn part1(input: impl BufRead) -> Result<usize, Error> {
+    let hailstones = read_hailstones(input)?;
 
     fn is_inside(i: &(f64, f64)) -> bool {
         let bounds_min: f64 = 200000000000000.0;
@@ -261,38 +235,33 @@ fn part1(input: &str) -> Result<(), Error> {
         }
     }
 
-    println!("Part 1: {}", count);
-    return Ok(());
+    return Ok(count);
 }
 
-fn part2(input: &str) -> Result<(), Error> {
-    let maybe_hailstones: Result<Vec<Hailstone>, Error> =
-        input.lines().map(|line| Hailstone::new(line)).collect();
-    let hailstones = maybe_hailstones?;
-
-    if let Some(stone) = search_stone_parameters(&hailstones, -500..500) {
-        println!("Stone: {} -> {}", stone.pos, stone.vel);
-        println!(
-            "Part 2: {}",
-            stone.pos.x.round() + stone.pos.y.round() + stone.pos.z.round()
-        );
-    } else {
-        println!("Part 2: no result")
-    }
+fn part2(input: impl BufRead) -> Result<f64, Error> {
+    let hailstones = read_hailstones(input)?;
 
-    return Ok(());
+    let stone = search_stone_parameters(&hailstones, -500..500).ok_or(Error::StoneNotFound)?;
+    println!("Stone: {} -> {}", stone.pos, stone.vel);
+    return Ok(stone.pos.x.round() + stone.pos.y.round() + stone.pos.z.round());
 }
 
 fn main() -> Result<(), Error> {
-    let input = include_str!("../rsc/input.txt");
-
-    let start1 = Instant::now();
-    part1(input)?;
-    println!("Elapsed: {:.2?}\n", start1.elapsed());
+    let options =
+        Options::from_args(include_str!("../rsc/input.txt")).map_err(Error::StreamError)?;
+    let open = || options.source.open().map_err(Error::StreamError);
+
+    if options.runs(1) {
+        let start1 = Instant::now();
+        println!("Part 1: {}", part1(open()?)?);
+        println!("Elapsed: {:.2?}\n", start1.elapsed());
+    }
 
-    let start2 = Instant::now();
-    part2(input)?;
-    println!("Elapsed: {:.2?}", start2.elapsed());
+    if options.runs(2) {
+        let start2 = Instant::now();
+        println!("Part 2: {}", part2(open()?)?);
+        println!("Elapsed: {:.2?}", start2.elapsed());
+    }
 
     Ok(())
 }
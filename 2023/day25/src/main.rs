@@ -0,0 +1,567 @@
+use aoc_common::anonymize::{self, Names};
+use aoc_common::assumptions::{self, Assumption, Check};
+use aoc_common::cli::{Args, UsageError};
+use aoc_common::graph::{Graph, NodeId};
+use aoc_common::parallel;
+use aoc_common::parse::{self, lines, pair, separated, space1, tag, terminated, word, ParseError};
+use aoc_common::stream::{Source, StreamError};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt::Display;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Instant,
+};
+
+#[derive(Debug)]
+enum Error {
+    ParseError(ParseError),
+    UsageError(UsageError),
+    AssumptionFailed,
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::UsageError(e) => write!(f, "{}", e),
+            Error::AssumptionFailed => {
+                write!(f, "the input doesn't hold the assumptions of the solution")
+            }
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Connection {
+    component1: NodeId,
+    component2: NodeId,
+}
+
+// "jqt: rhn xhk nvd"
+fn parse_lines(input: &str) -> Result<Vec<(&str, Vec<&str>)>, Error> {
+    let connections = pair(terminated(word(), tag(": ")), separated(word(), space1()));
+    parse::parse(lines(connections), input).map_err(Error::ParseError)
+}
+
+fn parse(input: &str) -> Result<Graph<&str>, Error> {
+    let mut graph = Graph::undirected();
+
+    let lines = parse_lines(input)?;
+
+    for (c1, others) in lines {
+        let i1 = graph.intern(c1);
+
+        for other in others {
+            let i2 = graph.intern(other);
+            graph.add_edge(i1, i2, 1);
+        }
+    }
+
+    Ok(graph)
+}
+
+fn group_product(graph: &Graph<&str>) -> usize {
+    graph
+        .connected_components()
+        .iter()
+        .fold(1, |acc, group| acc * group.len())
+}
+
+// The cut of my input, found by part 2.
+const PART1_CUTS: [(&str, &str); 3] = [("xhg", "ljl"), ("lkm", "ffj"), ("vgs", "xjb")];
+
+fn remove_connection(graph: &mut Graph<&str>, c1: &str, c2: &str) -> Option<()> {
+    let i1 = graph.id(c1)?;
+    let i2 = graph.id(c2)?;
+    if graph.remove_edge(i1, i2) {
+        Some(())
+    } else {
+        None
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct MergedConnection {
+    component1: usize,
+    component2: usize,
+    orig_component1: usize,
+    orig_component2: usize,
+}
+
+impl MergedConnection {
+    fn new(connection: &Connection) -> MergedConnection {
+        MergedConnection {
+            component1: connection.component1,
+            component2: connection.component2,
+            orig_component1: connection.component1,
+            orig_component2: connection.component2,
+        }
+    }
+
+    fn to_connection(&self) -> Connection {
+        Connection {
+            component1: self.orig_component1,
+            component2: self.orig_component2,
+        }
+    }
+
+    fn replace(&self, victim: usize, survivor: usize) -> MergedConnection {
+        if self.component1 == victim {
+            MergedConnection {
+                component1: survivor,
+                component2: self.component2,
+                orig_component1: self.orig_component1,
+                orig_component2: self.orig_component2,
+            }
+        } else if self.component2 == victim {
+            MergedConnection {
+                component1: self.component1,
+                component2: survivor,
+                orig_component1: self.orig_component1,
+                orig_component2: self.orig_component2,
+            }
+        } else {
+            panic!("Invalid replacement!")
+        }
+    }
+}
+
+// Part of Karger's Algorithm: contract a connection. Here, the simply remove the connection and
+// reroute all connections that target one of the nodes (the victim) to the other (the survivor).
+// The victim is removed from the list of components.
+fn contract(
+    connection: &MergedConnection,
+    components: &mut HashSet<usize>,
+    connections: &mut Vec<MergedConnection>,
+) {
+    let survivor = connection.component1;
+    let victim = connection.component2;
+
+    components.remove(&victim);
+    if let Some(index) = connections.iter().position(|c| c == connection) {
+        connections.remove(index);
+    } else {
+        panic!("Connection not in list!")
+    }
+
+    // Need to patch all connections involving the victim. If replacing the connection would
+    // result in a connection from the survivor to the survivor, the connection needs to be
+    // removed.
+    let mut need_replacement = Vec::new();
+    let mut self_referencing = Vec::new();
+    for (index, candidate) in connections
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.component1 == victim || c.component2 == victim)
+    {
+        if candidate.component1 == survivor || candidate.component2 == survivor {
+            self_referencing.push(index)
+        } else {
+            need_replacement.push(index);
+        }
+    }
+
+    for index in need_replacement {
+        let replacement = connections[index].replace(victim, survivor);
+        connections[index] = replacement;
+    }
+
+    self_referencing.sort();
+    self_referencing.reverse();
+    for index in self_referencing {
+        connections.remove(index);
+    }
+}
+
+// Find cuts using Karger's Algorithm.
+fn find_cuts(graph: &Graph<&str>, rng: &mut impl Rng) -> Vec<Connection> {
+    let mut remaining_connections: Vec<MergedConnection> = graph
+        .edge_list()
+        .iter()
+        .map(|(component1, component2, _)| {
+            MergedConnection::new(&Connection {
+                component1: *component1,
+                component2: *component2,
+            })
+        })
+        .collect();
+    let mut remaining_components: HashSet<usize> = (0..graph.len()).collect();
+
+    while remaining_components.len() > 2 {
+        let connection = remaining_connections.choose(rng).unwrap().clone();
+        contract(
+            &connection,
+            &mut remaining_components,
+            &mut remaining_connections,
+        );
+    }
+
+    remaining_connections
+        .iter()
+        .map(|c| c.to_connection())
+        .collect()
+}
+
+// Every trial gets its own stream of the generator, so a trial can be replayed from the seed and
+// its number alone, no matter which trials ran before it or on which thread.
+fn trial_rng(seed: u64, trial: u64) -> ChaCha8Rng {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    rng.set_stream(trial);
+    rng
+}
+
+struct MinCut {
+    cuts: Vec<Connection>,
+    trials: u64,
+}
+
+// Karger's Algorithm is random, it does not always find the optimal solution. We know the optimal
+// cut has three connections, so apply the algorithm until a cut with just three connections is
+// found. With the parallel feature, a batch of trials runs at once. The first successful trial is
+// taken either way, so the result only depends on the seed.
+fn find_three_cuts(graph: &Graph<&str>, seed: u64) -> MinCut {
+    #[cfg(feature = "parallel")]
+    let batch = rayon::current_num_threads() as u64;
+    #[cfg(not(feature = "parallel"))]
+    let batch = 1;
+
+    let mut first = 0;
+    loop {
+        let trials = first..first + batch;
+        #[cfg(feature = "parallel")]
+        let trials = trials.into_par_iter();
+
+        let results: Vec<_> = trials
+            .map(|trial| find_cuts(graph, &mut trial_rng(seed, trial)))
+            .collect();
+        for (trial, cuts) in (first..).zip(results) {
+            println!(
+                "Trial {} (seed {}, stream {}): cut of {} connections",
+                trial + 1,
+                seed,
+                trial,
+                cuts.len()
+            );
+            if cuts.len() == 3 {
+                return MinCut {
+                    cuts,
+                    trials: trial + 1,
+                };
+            }
+        }
+
+        first += batch;
+    }
+}
+
+// The number of edge-disjoint paths from `from` to `to`, counting no further than `limit`. By
+// Menger's theorem, that's the size of the smallest cut separating the two.
+fn disjoint_paths(graph: &Graph<&str>, from: NodeId, to: NodeId, limit: usize) -> usize {
+    // The flow along each connection in the given direction, the opposite one counts negative.
+    let mut flow: HashMap<(NodeId, NodeId), i32> = HashMap::new();
+
+    for paths in 0..limit {
+        let mut previous: HashMap<NodeId, NodeId> = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(id) = queue.pop_front() {
+            if id == to {
+                break;
+            }
+            for next in graph.neighbours(id) {
+                let used = flow.get(&(id, next)).copied().unwrap_or(0);
+                if used < 1 && !previous.contains_key(&next) {
+                    previous.insert(next, id);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if !previous.contains_key(&to) {
+            return paths;
+        }
+
+        let mut id = to;
+        while id != from {
+            let prev = previous[&id];
+            *flow.entry((prev, id)).or_insert(0) += 1;
+            *flow.entry((id, prev)).or_insert(0) -= 1;
+            id = prev;
+        }
+    }
+
+    limit
+}
+
+fn check_minimum_cut(input: &str) -> Check {
+    let graph = parse(input).map_err(|e| e.to_string())?;
+    if graph.len() < 2 {
+        return Err(format!("there are {} components", graph.len()));
+    }
+
+    // Every cut separates the first component from some other one.
+    let minimum = (1..graph.len())
+        .map(|id| disjoint_paths(&graph, 0, id, 4))
+        .min()
+        .unwrap_or(0);
+    match minimum {
+        3 => Ok(()),
+        4 => Err("the smallest cut has more than 3 connections".to_string()),
+        _ => Err(format!("there is a cut of just {}", minimum)),
+    }
+}
+
+fn check_two_groups(input: &str) -> Check {
+    let mut graph = parse(input).map_err(|e| e.to_string())?;
+    for (c1, c2) in PART1_CUTS {
+        if remove_connection(&mut graph, c1, c2).is_none() {
+            return Err(format!("there is no connection {} -- {}", c1, c2));
+        }
+    }
+
+    match graph.connected_components().len() {
+        2 => Ok(()),
+        groups => Err(format!("cutting them leaves {} groups", groups)),
+    }
+}
+
+// Part 2 keeps trying Karger's Algorithm until it finds a cut of three connections, which would
+// never end if there's none. Part 1 cuts connections found that way in an earlier run.
+const ASSUMPTIONS: [Assumption<str>; 2] = [
+    Assumption {
+        description: "the smallest cut has exactly 3 connections",
+        check: check_minimum_cut,
+    },
+    Assumption {
+        description: "the connections cut in part 1 split the components into two groups",
+        check: check_two_groups,
+    },
+];
+
+fn made_up_name(rng: &mut anonymize::Rng, name: &str) -> String {
+    rng.pick("abcdefghijklmnopqrstuvwxyz", name.len().max(3))
+}
+
+// The same wiring with other names, in another order. The components of the cut of part 1 keep
+// their names, so it still applies.
+fn anonymize(input: &str, seed: u64) -> Result<String, Error> {
+    let mut lines = parse_lines(input)?;
+    let mut names = Names::new(seed);
+    for (c1, c2) in PART1_CUTS {
+        names.keep(c1);
+        names.keep(c2);
+    }
+    names.rng().shuffle(&mut lines);
+
+    let mut anonymized = String::new();
+    for (component, mut others) in lines {
+        names.rng().shuffle(&mut others);
+        let others: Vec<String> = others
+            .iter()
+            .map(|o| names.rename(o, made_up_name))
+            .collect();
+        anonymized += &format!(
+            "{}: {}\n",
+            names.rename(component, made_up_name),
+            others.join(" ")
+        );
+    }
+    Ok(anonymized)
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    let mut graph = parse(input)?;
+
+    for (c1, c2) in PART1_CUTS {
+        remove_connection(&mut graph, c1, c2);
+    }
+
+    return Ok(group_product(&graph));
+}
+
+fn part2(input: &str, seed: u64) -> Result<usize, Error> {
+    let mut graph = parse(input)?;
+
+    println!("Seed: {}", seed);
+    let min_cut = find_three_cuts(&graph, seed);
+    println!("Trials: {}", min_cut.trials);
+
+    for cut in min_cut.cuts {
+        let n1 = graph.node(cut.component1);
+        let n2 = graph.node(cut.component2);
+        println!("Found cut {} -- {}", n1, n2);
+
+        if !graph.remove_edge(cut.component1, cut.component2) {
+            panic!("Did not find cut!");
+        }
+    }
+
+    return Ok(group_product(&graph));
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = Args::from_env();
+    parallel::init_threads(&mut args).map_err(Error::UsageError)?;
+    // Random unless given, but always reported so the run can be repeated.
+    let seed = args.value("--seed").map_err(Error::UsageError)?;
+    let seed = seed.unwrap_or_else(rand::random);
+    let check_assumptions = args.flag("--check-assumptions");
+    let anonymize_seed: Option<u64> = args.value("--anonymize").map_err(Error::UsageError)?;
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    if let Some(seed) = anonymize_seed {
+        print!("{}", anonymize(&input, seed)?);
+        return Ok(());
+    }
+
+    if check_assumptions {
+        if !assumptions::report(input.as_str(), &ASSUMPTIONS) {
+            return Err(Error::AssumptionFailed);
+        }
+        println!();
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 1: {}", part2(&input, seed)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+    const SEED: u64 = 2;
+
+    fn cut_names(graph: &Graph<&str>, min_cut: &MinCut) -> Vec<(String, String)> {
+        let mut names: Vec<_> = min_cut
+            .cuts
+            .iter()
+            .map(|c| {
+                let mut pair = [graph.node(c.component1), graph.node(c.component2)];
+                pair.sort();
+                (pair[0].to_string(), pair[1].to_string())
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn fixed_seed_finds_minimum_cut() {
+        let mut graph = parse(SAMPLE).unwrap();
+        let min_cut = find_three_cuts(&graph, SEED);
+
+        assert_eq!(min_cut.trials, 12);
+        assert_eq!(
+            cut_names(&graph, &min_cut),
+            [("bvb", "cmg"), ("hfx", "pzl"), ("jqt", "nvd")]
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+        );
+
+        for cut in min_cut.cuts {
+            assert!(graph.remove_edge(cut.component1, cut.component2));
+        }
+        assert_eq!(group_product(&graph), 54);
+    }
+
+    #[test]
+    fn same_seed_replays_same_trials() {
+        let graph = parse(SAMPLE).unwrap();
+        for seed in [0, 1, 17] {
+            let first = find_three_cuts(&graph, seed);
+            let second = find_three_cuts(&graph, seed);
+            assert_eq!(first.trials, second.trials);
+            assert!(first.cuts == second.cuts);
+        }
+    }
+
+    #[test]
+    fn anonymizing_keeps_the_answers() {
+        for seed in 0..10 {
+            let anonymized = anonymize(SAMPLE, seed).unwrap();
+            assert_eq!(part2(&anonymized, SEED).unwrap(), 54);
+            assert_eq!(anonymized, anonymize(SAMPLE, seed).unwrap());
+            assert_ne!(anonymized, SAMPLE);
+        }
+
+        // The cut of part 1 stays where it is.
+        let anonymized = anonymize("xhg: ljl\nlkm: ffj\n", 0).unwrap();
+        assert!(anonymized.contains("xhg: ljl") && anonymized.contains("lkm: ffj"));
+    }
+
+    // Which of the assumptions hold for the input, in their order.
+    fn holding(input: &str) -> Vec<bool> {
+        ASSUMPTIONS
+            .iter()
+            .map(|assumption| (assumption.check)(input).is_ok())
+            .collect()
+    }
+
+    #[test]
+    fn assumptions_on_crafted_inputs() {
+        // The cut of part 1 belongs to another input.
+        assert_eq!(holding(SAMPLE), [true, false]);
+        assert_eq!(
+            check_two_groups(SAMPLE),
+            Err("there is no connection xhg -- ljl".to_string())
+        );
+
+        // Two triangles, joined by the cut of part 1.
+        let prism = "xhg: ljl lkm vgs\nlkm: ffj vgs\nvgs: xjb\nljl: ffj xjb\nffj: xjb\n";
+        assert_eq!(holding(prism), [true, true]);
+
+        // Without ffj -- xjb, ffj only has two connections.
+        let open = "xhg: ljl lkm vgs\nlkm: ffj vgs\nvgs: xjb\nljl: ffj xjb\n";
+        assert_eq!(holding(open), [false, true]);
+        assert_eq!(
+            check_minimum_cut(open),
+            Err("there is a cut of just 2".to_string())
+        );
+
+        // Triangles joined by six connections leave one group.
+        let joined =
+            "xhg: ljl lkm vgs ffj\nlkm: ffj vgs xjb\nvgs: xjb ljl\nljl: ffj xjb\nffj: xjb\n";
+        assert_eq!(holding(joined), [false, false]);
+        assert_eq!(
+            check_minimum_cut(joined),
+            Err("the smallest cut has more than 3 connections".to_string())
+        );
+    }
+
+    #[test]
+    fn trial_only_depends_on_seed_and_number() {
+        let graph = parse(SAMPLE).unwrap();
+        let cuts = find_cuts(&graph, &mut trial_rng(SEED, 11));
+        assert_eq!(cuts.len(), 3);
+        assert!(cuts == find_cuts(&graph, &mut trial_rng(SEED, 11)));
+    }
+}
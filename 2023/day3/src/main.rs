@@ -0,0 +1,193 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{self, grid, ParseError};
+use aoc_common::stream::Source;
+use aoc_common::vector::Vec2;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::time::Instant;
+
+#[derive(Hash, PartialEq, Eq, Clone)]
+struct EntryLong {
+    x1: i32,
+    x2: i32,
+    y: i32,
+    num: i32,
+}
+
+fn surrounding(coord: &Vec2<i32>, number: i32) -> Vec<Vec2<i32>> {
+    let digits: i32 = (number as f64).log10().floor() as i32;
+    let mut result = Vec::new();
+
+    for y in coord.y - 1..=coord.y + 1 {
+        for x in coord.x - 1..=coord.x + digits + 1 {
+            result.push(Vec2::new(x, y));
+        }
+    }
+
+    return result;
+}
+
+fn has_symbol_neighbour(
+    coord: &Vec2<i32>,
+    number: i32,
+    symbols: &HashMap<Vec2<i32>, char>,
+) -> bool {
+    for neighbour in surrounding(coord, number) {
+        if symbols.contains_key(&neighbour) {
+            return true;
+        }
+    }
+
+    return false;
+}
+
+fn insert_long(numbers: &mut HashMap<Vec2<i32>, EntryLong>, entry: EntryLong) {
+    for x in entry.x1..=entry.x2 {
+        let new_entry = entry.clone();
+        numbers.insert(Vec2::new(x, new_entry.y), new_entry);
+    }
+}
+
+fn gear_ratio(coord: &Vec2<i32>, numbers: &HashMap<Vec2<i32>, EntryLong>) -> i32 {
+    let mut candidates: HashSet<EntryLong> = HashSet::new();
+
+    for neighbour in surrounding(coord, 1) {
+        if let Some(entry) = numbers.get(&neighbour) {
+            candidates.insert(entry.clone());
+        }
+    }
+
+    if candidates.len() != 2 {
+        return 0;
+    }
+
+    return candidates.iter().fold(1, |a, e| a * e.num);
+}
+
+enum Cell {
+    Empty,
+    Digit(i32),
+    Symbol(char),
+}
+
+fn cell(c: char) -> Option<Cell> {
+    match c {
+        '.' => Some(Cell::Empty),
+        '0'..='9' => c.to_digit(10).map(|d| Cell::Digit(d as i32)),
+        _ if c.is_ascii_punctuation() => Some(Cell::Symbol(c)),
+        _ => None,
+    }
+}
+
+struct Schematic {
+    symbols: HashMap<Vec2<i32>, char>,
+    numbers: Vec<EntryLong>,
+}
+
+fn parse(input: &str) -> Result<Schematic, ParseError> {
+    let rows = parse::parse(grid("schematic cell", cell), input)?;
+    let mut symbols: HashMap<Vec2<i32>, char> = HashMap::new();
+    let mut numbers: Vec<EntryLong> = Vec::new();
+
+    for (y, row) in rows.iter().enumerate() {
+        let y = y as i32;
+        let mut start = -1;
+        let mut num = 0;
+        // The extra empty cell terminates a number at the end of the row.
+        for (x, cell) in row.iter().chain([&Cell::Empty]).enumerate() {
+            let x = x as i32;
+            if let Cell::Digit(digit) = cell {
+                if start < 0 {
+                    start = x;
+                }
+                num = num * 10 + digit;
+                continue;
+            }
+
+            if start >= 0 {
+                numbers.push(EntryLong {
+                    x1: start,
+                    x2: x - 1,
+                    y,
+                    num,
+                });
+                num = 0;
+                start = -1;
+            }
+
+            if let Cell::Symbol(char) = cell {
+                symbols.insert(Vec2::new(x, y), *char);
+            }
+        }
+    }
+
+    Ok(Schematic { symbols, numbers })
+}
+
+fn part1(input: &str) -> Result<i32, Box<dyn Error>> {
+    let schematic = parse(input)?;
+
+    let valid = schematic
+        .numbers
+        .iter()
+        .filter(|e| has_symbol_neighbour(&Vec2::new(e.x1, e.y), e.num, &schematic.symbols));
+    let sum: i32 = valid.map(|e| e.num).fold(0, |a, v| a + v);
+
+    return Ok(sum);
+}
+
+fn part2(input: &str) -> Result<i32, Box<dyn Error>> {
+    let schematic = parse(input)?;
+    let mut numbers: HashMap<Vec2<i32>, EntryLong> = HashMap::new();
+    for entry in schematic.numbers {
+        insert_long(&mut numbers, entry);
+    }
+
+    let ratios = schematic
+        .symbols
+        .iter()
+        .filter(|(_, char)| **char == '*')
+        .map(|(coord, _)| gear_ratio(coord, &numbers));
+    let sum = ratios.fold(0, |a, r| a + r);
+
+    return Ok(sum);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))?.read()?;
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE).unwrap(), 4361);
+        assert_eq!(part2(SAMPLE).unwrap(), 467835);
+    }
+}
@@ -0,0 +1,126 @@
+use aoc_common::checked::Arith;
+use aoc_common::parse::{
+    delimited, integer, pair, preceded, separated, space0, space1, tag, terminated, Input,
+    ParseResult,
+};
+use aoc_common::stream::{self, Options};
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::io::BufRead;
+use std::time::Instant;
+
+const PART1: Arith = Arith::new("2023/day4", 1);
+const PART2: Arith = Arith::new("2023/day4", 2);
+
+struct Card {
+    winning: Vec<i32>,
+    candidates: Vec<i32>,
+}
+
+// "Card 1: 41 48 83 | 83 86  6 31"
+fn card(input: Input) -> ParseResult<Card> {
+    let numbers = || delimited(space0(), separated(integer(), space1()), space0());
+    let id = delimited(pair(tag("Card"), space1()), integer::<u32>(), tag(":"));
+    let (numbers, rest) = preceded(id, pair(terminated(numbers(), tag("|")), numbers()))(input)?;
+    Ok((
+        Card {
+            winning: numbers.0,
+            candidates: numbers.1,
+        },
+        rest,
+    ))
+}
+
+fn part1(input: impl BufRead) -> Result<u32, Box<dyn Error>> {
+    let mut total = 0;
+
+    for record in stream::lines(input) {
+        let card = record?.parse(card)?;
+        let winning = card.winning.iter();
+        let candidates = card.candidates;
+        let matches = winning.filter(|w| candidates.contains(w));
+        let count = matches.count();
+
+        if count > 0 {
+            let points = PART1.shl(1, count as u32 - 1)?;
+            total = PART1.add(total, points)?;
+        }
+    }
+
+    return Ok(total);
+}
+
+fn part2(input: impl BufRead) -> Result<usize, Box<dyn Error>> {
+    let mut total = 0;
+    // Copies won by the previous cards, for the cards that follow. Only reaches as far as the
+    // most matches of a card, no matter how many cards there are.
+    let mut copies: VecDeque<usize> = VecDeque::new();
+
+    for record in stream::lines(input) {
+        let card = record?.parse(card)?;
+        let winning: HashSet<i32> = card.winning.into_iter().collect();
+        let candidates: HashSet<i32> = card.candidates.into_iter().collect();
+        let matches = winning.intersection(&candidates);
+        let count = matches.count();
+
+        let instances = PART2.add(1, copies.pop_front().unwrap_or(0))?;
+        total = PART2.add(total, instances)?;
+
+        if copies.len() < count {
+            copies.resize(count, 0);
+        }
+        for next in copies.iter_mut().take(count) {
+            *next = PART2.add(*next, instances)?;
+        }
+    }
+
+    return Ok(total);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = Options::from_args(include_str!("../rsc/input.txt"))?;
+
+    if options.runs(1) {
+        let start1 = Instant::now();
+        println!("Part 1: {}", part1(options.source.open()?)?);
+        println!("Elapsed: {:.2?}\n", start1.elapsed());
+    }
+
+    if options.runs(2) {
+        let start2 = Instant::now();
+        println!("Part 2: {}", part2(options.source.open()?)?);
+        println!("Elapsed: {:.2?}", start2.elapsed());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE.as_bytes()).unwrap(), 13);
+        assert_eq!(part2(SAMPLE.as_bytes()).unwrap(), 30);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn overflow_is_an_error() {
+        // 33 matches are worth 1 << 32 points, which doesn't fit a u32.
+        let numbers: Vec<String> = (1..=33).map(|n| n.to_string()).collect();
+        let card = format!("Card 1: {} | {}\n", numbers.join(" "), numbers.join(" "));
+        let error = part1(card.as_bytes()).unwrap_err().to_string();
+        assert_eq!(error, "2023/day4 part 1: 1 << 32 in u32 overflows");
+    }
+}
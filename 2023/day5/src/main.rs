@@ -0,0 +1,402 @@
+use aoc_common::cli::Args;
+use aoc_common::interval::{Interval, IntervalSet};
+use aoc_common::model::{self, Loaded};
+use aoc_common::parallel;
+use aoc_common::parse::{
+    self, blocks, integer, line_end, lines, pair, preceded, separated, space1, tag, terminated,
+    word, Input, ParseError, ParseResult,
+};
+use aoc_common::solvers::{self, Solver};
+use aoc_common::stream::Source;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::Instant;
+
+const PUZZLE: &str = "2023/day5";
+const MODEL_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct SeedRange {
+    source: Interval<i64>,
+    offset: i64,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+struct Index {
+    from: String,
+    to: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Almanac {
+    seeds: Vec<i64>,
+    mappings: HashMap<Index, Vec<SeedRange>>,
+}
+
+impl SeedRange {
+    fn map(&self, value: i64) -> Option<i64> {
+        if !self.source.contains(value) {
+            return None;
+        }
+
+        Some(value + self.offset)
+    }
+}
+
+fn map_value(value: i64, ranges: &Vec<SeedRange>) -> i64 {
+    for range in ranges {
+        if let Some(mapped) = range.map(value) {
+            return mapped;
+        }
+    }
+
+    value
+}
+
+// Same as `map_value`, but for whole sets of values: the parts covered by a range are shifted, the
+// rest is passed through unchanged.
+fn map_values(values: &IntervalSet<i64>, ranges: &Vec<SeedRange>) -> IntervalSet<i64> {
+    let mut remaining = values.clone();
+    let mut mapped = IntervalSet::new();
+
+    for range in ranges {
+        let source = IntervalSet::from(range.source);
+        mapped = mapped.union(&remaining.intersection(&source).shift(range.offset));
+        remaining = remaining.difference(&source);
+    }
+
+    mapped.union(&remaining)
+}
+
+fn find_mapping<'a>(
+    source: &str,
+    mappings: &'a HashMap<Index, Vec<SeedRange>>,
+) -> Option<(&'a Index, &'a Vec<SeedRange>)> {
+    for (index, ranges) in mappings {
+        if index.from == source {
+            return Some((index, ranges));
+        }
+    }
+
+    None
+}
+
+fn find_location(seed: i64, mappings: &HashMap<Index, Vec<SeedRange>>) -> i64 {
+    let mut source = "seed";
+    let mut value = seed;
+
+    while let Some((index, ranges)) = find_mapping(source, mappings) {
+        value = map_value(value, ranges);
+        if index.to == "location" {
+            return value;
+        }
+
+        source = &index.to;
+    }
+
+    panic!("No end!")
+}
+
+fn find_locations(
+    seeds: &IntervalSet<i64>,
+    mappings: &HashMap<Index, Vec<SeedRange>>,
+) -> IntervalSet<i64> {
+    let mut source = "seed";
+    let mut values = seeds.clone();
+
+    while let Some((index, ranges)) = find_mapping(source, mappings) {
+        values = map_values(&values, ranges);
+        if index.to == "location" {
+            return values;
+        }
+
+        source = &index.to;
+    }
+
+    panic!("No end!")
+}
+
+// "50 98 2", meaning 98..100 is mapped to 50..52.
+fn seed_range(input: Input) -> ParseResult<SeedRange> {
+    let number = || integer::<i64>();
+    let (numbers, rest) = pair(
+        terminated(number(), space1()),
+        pair(terminated(number(), space1()), number()),
+    )(input)?;
+    let (dest, (source, length)) = numbers;
+    Ok((
+        SeedRange {
+            source: Interval::new(source, source + length),
+            offset: dest - source,
+        },
+        rest,
+    ))
+}
+
+// "seed-to-soil map:" followed by the ranges.
+fn mapping(input: Input) -> ParseResult<(Index, Vec<SeedRange>)> {
+    let header = pair(
+        terminated(word(), tag("-to-")),
+        terminated(word(), tag(" map:")),
+    );
+    let ((names, ranges), rest) = pair(terminated(header, line_end()), lines(seed_range))(input)?;
+    let index = Index {
+        from: names.0.to_string(),
+        to: names.1.to_string(),
+    };
+    Ok(((index, ranges), rest))
+}
+
+fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let seeds = preceded(tag("seeds: "), separated(integer(), space1()));
+    let almanac = pair(
+        terminated(seeds, pair(line_end(), line_end())),
+        blocks(mapping),
+    );
+    let (seeds, mappings) = parse::parse(almanac, input)?;
+
+    Ok(Almanac {
+        seeds,
+        mappings: mappings.into_iter().collect(),
+    })
+}
+
+fn lowest_location(almanac: &Almanac) -> Result<i64, Box<dyn Error>> {
+    let mut lowest = i64::max_value();
+    for &seed in &almanac.seeds {
+        let loc = find_location(seed, &almanac.mappings);
+        lowest = lowest.min(loc);
+    }
+
+    return Ok(lowest);
+}
+
+fn seed_ranges(seeds: &[i64]) -> Vec<Interval<i64>> {
+    seeds
+        .chunks(2)
+        .map(|c| Interval::new(c[0], c[0] + c[1]))
+        .collect()
+}
+
+// Used to take 3min by mapping every single seed. Mapping whole ranges instead, by splitting and
+// shifting them, is instant.
+fn lowest_location_of_ranges(almanac: &Almanac) -> Result<i64, Box<dyn Error>> {
+    let seed_ranges = seed_ranges(&almanac.seeds);
+
+    // Each seed range is mapped on its own, so they can be spread over threads.
+    #[cfg(feature = "parallel")]
+    let seed_ranges = seed_ranges.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let seed_ranges = seed_ranges.into_iter();
+
+    let lowest = seed_ranges
+        .filter_map(|seeds| find_locations(&IntervalSet::from(seeds), &almanac.mappings).min())
+        .min()
+        .unwrap_or(i64::max_value());
+
+    return Ok(lowest);
+}
+
+// The original solution, mapping every single seed.
+fn lowest_location_per_seed(almanac: &Almanac) -> Result<i64, Box<dyn Error>> {
+    let seed_ranges = seed_ranges(&almanac.seeds);
+
+    #[cfg(feature = "parallel")]
+    let seed_ranges = seed_ranges.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let seed_ranges = seed_ranges.into_iter();
+
+    let lowest = seed_ranges
+        .filter_map(|seeds| {
+            (seeds.start..seeds.end)
+                .map(|seed| find_location(seed, &almanac.mappings))
+                .min()
+        })
+        .min()
+        .unwrap_or(i64::max_value());
+
+    return Ok(lowest);
+}
+
+const SOLVERS: [Solver<Almanac, i64, Box<dyn Error>>; 3] = [
+    Solver {
+        part: 1,
+        name: "per seed",
+        solve: lowest_location,
+    },
+    Solver {
+        part: 2,
+        name: "ranges",
+        solve: lowest_location_of_ranges,
+    },
+    Solver {
+        part: 2,
+        name: "per seed",
+        solve: lowest_location_per_seed,
+    },
+];
+
+fn part1(almanac: &Almanac) -> Result<i64, Box<dyn Error>> {
+    return lowest_location(almanac);
+}
+
+fn part2(almanac: &Almanac) -> Result<i64, Box<dyn Error>> {
+    return lowest_location_of_ranges(almanac);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = Args::from_env();
+    parallel::init_threads(&mut args)?;
+    let cross_check = args.flag("--cross-check");
+    let save_model: Option<PathBuf> = args.value("--save-model")?;
+    let source = Source::from_args(args, include_str!("../rsc/input.txt"))?;
+
+    let start = Instant::now();
+    let almanac = match model::read(&source, PUZZLE, MODEL_VERSION)? {
+        Loaded::Model(almanac) => almanac,
+        Loaded::Text(input) => parse(&input)?,
+    };
+    println!("Input: {:.2?}\n", start.elapsed());
+
+    if let Some(path) = save_model {
+        model::save(&path, PUZZLE, MODEL_VERSION, &almanac)?;
+        println!("Saved the model to {}", path.display());
+        return Ok(());
+    }
+
+    if cross_check {
+        if !solvers::cross_check(&almanac, &SOLVERS)? {
+            return Err("the solvers disagree".into());
+        }
+        return Ok(());
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&almanac)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&almanac)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const CATEGORIES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+
+    // The lines "destination source length" of one map. Like in the puzzle, the destinations are
+    // the sources rearranged: blocks covering 0..total, moved into a shuffled order.
+    fn map_lines() -> impl Strategy<Value = Vec<(i64, i64, i64)>> {
+        prop::collection::vec(1..20i64, 1..6)
+            .prop_flat_map(|lengths| {
+                let order: Vec<usize> = (0..lengths.len()).collect();
+                (Just(lengths), Just(order).prop_shuffle())
+            })
+            .prop_map(|(lengths, order)| {
+                let mut sources = vec![0; lengths.len()];
+                for i in 1..lengths.len() {
+                    sources[i] = sources[i - 1] + lengths[i - 1];
+                }
+                let mut destinations = vec![0; lengths.len()];
+                let mut next = 0;
+                for i in order {
+                    destinations[i] = next;
+                    next += lengths[i];
+                }
+
+                (0..lengths.len())
+                    .map(|i| (destinations[i], sources[i], lengths[i]))
+                    .collect()
+            })
+    }
+
+    fn almanac() -> impl Strategy<Value = String> {
+        let seeds = prop::collection::vec((0..120i64, 1..30i64), 1..4);
+        let maps = prop::collection::vec(map_lines(), CATEGORIES.len() - 1);
+        (seeds, maps).prop_map(|(seeds, maps)| {
+            let seeds: Vec<String> = seeds
+                .iter()
+                .map(|(start, len)| format!("{} {}", start, len))
+                .collect();
+            let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+            for (i, lines) in maps.iter().enumerate() {
+                almanac += &format!("\n{}-to-{} map:\n", CATEGORIES[i], CATEGORIES[i + 1]);
+                for (destination, source, length) in lines {
+                    almanac += &format!("{} {} {}\n", destination, source, length);
+                }
+            }
+            almanac
+        })
+    }
+
+    fn seed_ranges(lines: &[(i64, i64, i64)]) -> Vec<SeedRange> {
+        lines
+            .iter()
+            .map(|(destination, source, length)| SeedRange {
+                source: Interval::new(*source, source + length),
+                offset: destination - source,
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn mapping_is_a_permutation(lines in map_lines()) {
+            let ranges = seed_ranges(&lines);
+            let total: i64 = lines.iter().map(|(_, _, length)| length).sum();
+
+            let mut mapped: Vec<i64> = (0..total).map(|v| map_value(v, &ranges)).collect();
+            mapped.sort();
+            prop_assert_eq!(mapped, (0..total).collect::<Vec<i64>>());
+            // Values not covered by the map stay where they are.
+            prop_assert_eq!(map_value(total, &ranges), total);
+            prop_assert_eq!(map_value(-1, &ranges), -1);
+
+            let all = IntervalSet::from(Interval::new(0, total));
+            prop_assert_eq!(map_values(&all, &ranges), all);
+        }
+
+        #[test]
+        fn mapping_ranges_keeps_their_size(almanac in almanac(), start in 0..150i64, len in 1..50i64) {
+            let mappings = parse(&almanac).unwrap().mappings;
+            let seeds = IntervalSet::from(Interval::new(start, start + len));
+            let locations = find_locations(&seeds, &mappings);
+
+            prop_assert_eq!(locations.len(), len);
+            for seed in start..start + len {
+                prop_assert!(locations.contains(find_location(seed, &mappings)));
+            }
+        }
+
+        #[test]
+        fn solvers_agree(almanac in almanac()) {
+            let almanac = parse(&almanac).unwrap();
+            for solver in &SOLVERS {
+                let expected = if solver.part == 1 {
+                    lowest_location(&almanac).unwrap()
+                } else {
+                    lowest_location_of_ranges(&almanac).unwrap()
+                };
+                prop_assert_eq!((solver.solve)(&almanac).unwrap(), expected);
+            }
+        }
+    }
+}
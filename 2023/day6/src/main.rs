@@ -0,0 +1,69 @@
+use aoc_common::cli::Args;
+use aoc_common::parse::{
+    self, integer, line_end, pair, preceded, separated, space1, tag, terminated, ParseError,
+};
+use aoc_common::stream::Source;
+use std::error::Error;
+use std::time::Instant;
+
+struct Races {
+    times: Vec<i64>,
+    distances: Vec<i64>,
+}
+
+// "Time:      7  15   30" followed by "Distance:  9  40  200"
+fn parse(input: &str) -> Result<Races, ParseError> {
+    let record = |name| preceded(pair(tag(name), space1()), separated(integer(), space1()));
+    let (times, distances) = parse::parse(
+        pair(terminated(record("Time:"), line_end()), record("Distance:")),
+        input,
+    )?;
+    Ok(Races { times, distances })
+}
+
+// Part 2 reads the numbers of a record as a single one, ignoring the spaces.
+fn join_digits(numbers: &[i64]) -> Result<i64, Box<dyn Error>> {
+    let digits: String = numbers.iter().map(|n| n.to_string()).collect();
+    Ok(digits.parse()?)
+}
+
+fn part1(input: &str) -> Result<usize, Box<dyn Error>> {
+    let races = parse(input)?;
+    let mut result: usize = 1;
+
+    for (&time, &distance) in races.times.iter().zip(races.distances.iter()) {
+        let candidates = (1..time).map(|t| (time - t) * t);
+        let valid = candidates.filter(|d| *d > distance).count();
+        result = result * valid;
+    }
+
+    return Ok(result);
+}
+
+fn part2(input: &str) -> Result<usize, Box<dyn Error>> {
+    let races = parse(input)?;
+    let time = join_digits(&races.times)?;
+    let distance = join_digits(&races.distances)?;
+    let mut result: usize = 1;
+
+    let candidates = (1..time).map(|t| (time - t) * t);
+    let valid = candidates.filter(|d| *d > distance).count();
+    result = result * valid;
+
+    return Ok(result);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))?.read()?;
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
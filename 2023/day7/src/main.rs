@@ -0,0 +1,344 @@
+use aoc_common::anonymize::Rng;
+use aoc_common::checked::{Arith, Overflow};
+use aoc_common::cli::Args;
+use aoc_common::model::{self, Loaded};
+use aoc_common::parse::{
+    self, integer, lines, many1, map, pair, space1, symbol, terminated, try_map, Input, ParseError,
+    ParseResult,
+};
+use aoc_common::stream::Source;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Instant;
+
+const PUZZLE: &str = "2023/day7";
+const MODEL_VERSION: u32 = 1;
+
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Serialize, Deserialize)]
+enum Strength {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Hand {
+    cards: [i32; 5],
+    strength: Strength,
+    bid: i32,
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let strength_order = self.strength.cmp(&other.strength);
+        match strength_order {
+            Ordering::Less => return Ordering::Less,
+            Ordering::Greater => return Ordering::Greater,
+            Ordering::Equal => (),
+        }
+
+        for i in 0..5 {
+            let card_order = self.cards[i].cmp(&other.cards[i]);
+            match card_order {
+                Ordering::Less => return Ordering::Less,
+                Ordering::Greater => return Ordering::Greater,
+                Ordering::Equal => (),
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+const CARDS: &str = "23456789TJQKA";
+
+// "32T3K 765", like in the input.
+impl Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for card in self.cards {
+            // Jokers are worth 0.
+            let c = match card {
+                0 => 'J',
+                _ => CARDS
+                    .chars()
+                    .nth(card as usize - 2)
+                    .ok_or(std::fmt::Error)?,
+            };
+            write!(f, "{}", c)?;
+        }
+        write!(f, " {}", self.bid)
+    }
+}
+
+fn char_to_value(c: char) -> Option<i32> {
+    match c {
+        '2'..='9' => Some((c as i32) - 48),
+        'T' => Some(10),
+        'J' => Some(11),
+        'Q' => Some(12),
+        'K' => Some(13),
+        'A' => Some(14),
+        _ => None,
+    }
+}
+
+fn cards_to_strength(cards: [i32; 5]) -> Strength {
+    let mut counts: [i32; 5] = [0; 5];
+    let mut mapping: [i32; 16] = [-1; 16];
+    let mut distinct: i32 = 0;
+
+    // Count how many distinct cards there are.
+    for card in cards {
+        let index: i32 = mapping[card as usize];
+        if index == -1 {
+            mapping[card as usize] = distinct;
+            counts[distinct as usize] += 1;
+            distinct += 1;
+        } else {
+            counts[index as usize] += 1
+        }
+    }
+
+    let joker_i = mapping[0];
+    if joker_i != -1 && distinct > 1 {
+        // Handle Jokers. "Fold" them into the highest count and remove them.
+        let mut max_i = 0;
+        let mut max_count = 0;
+        for k in 0..distinct {
+            if k == joker_i {
+                continue;
+            }
+
+            let count = counts[k as usize];
+            if count > max_count {
+                max_i = k;
+                max_count = count;
+            }
+        }
+
+        counts[max_i as usize] += counts[joker_i as usize];
+        if joker_i < (distinct - 1) {
+            counts[joker_i as usize] = counts[(distinct - 1) as usize];
+        }
+        distinct -= 1;
+    }
+
+    match distinct {
+        1 => Strength::FiveOfAKind,
+        2 => {
+            if counts[0] == 1 || counts[1] == 1 {
+                Strength::FourOfAKind
+            } else {
+                Strength::FullHouse
+            }
+        }
+        3 => {
+            if counts[0] == 3 || counts[1] == 3 || counts[2] == 3 {
+                Strength::ThreeOfAKind
+            } else {
+                Strength::TwoPair
+            }
+        }
+        4 => Strength::OnePair,
+        5 => Strength::HighCard,
+        _ => panic!("Cannot be reached"),
+    }
+}
+
+// The same hand, with its jacks turned into jokers: the weakest card, but they count as whatever
+// makes the hand strongest.
+fn with_jokers(hand: &Hand) -> Hand {
+    let cards = hand.cards.map(|card| if card == 11 { 0 } else { card });
+    Hand {
+        cards,
+        strength: cards_to_strength(cards),
+        bid: hand.bid,
+    }
+}
+
+// "32T3K 765"
+fn hand(input: Input) -> ParseResult<Hand> {
+    let cards = try_map(
+        many1(symbol("card", char_to_value)),
+        "five cards",
+        |cards: Vec<i32>| <[i32; 5]>::try_from(cards).ok(),
+    );
+    map(
+        pair(terminated(cards, space1()), integer()),
+        |(cards, bid)| Hand {
+            cards,
+            strength: cards_to_strength(cards),
+            bid,
+        },
+    )(input)
+}
+
+fn parse(input: &str) -> Result<Vec<Hand>, ParseError> {
+    parse::parse(lines(hand), input)
+}
+
+// The same hands in another order, which doesn't change how they rank.
+fn anonymize(hands: &[Hand], seed: u64) -> String {
+    let mut lines: Vec<String> = hands.iter().map(|h| h.to_string()).collect();
+    Rng::new(seed).shuffle(&mut lines);
+    lines.iter().map(|l| format!("{}\n", l)).collect()
+}
+
+// The bids of the sorted hands, each times its rank.
+fn winnings<'a>(sorted: impl Iterator<Item = &'a Hand>, arith: Arith) -> Result<i32, Overflow> {
+    sorted.zip(1..).try_fold(0, |acc, (hand, rank)| {
+        arith.add(acc, arith.mul(hand.bid, rank)?)
+    })
+}
+
+fn part1(hands: &[Hand]) -> Result<i32, Box<dyn Error>> {
+    let mut hands: Vec<&Hand> = hands.iter().collect();
+    hands.sort();
+
+    return Ok(winnings(hands.into_iter(), Arith::new(PUZZLE, 1))?);
+}
+
+fn part2(hands: &[Hand]) -> Result<i32, Box<dyn Error>> {
+    let mut hands: Vec<Hand> = hands.iter().map(with_jokers).collect();
+    hands.sort();
+
+    return Ok(winnings(hands.iter(), Arith::new(PUZZLE, 2))?);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = Args::from_env();
+    let save_model: Option<PathBuf> = args.value("--save-model")?;
+    let anonymize_seed: Option<u64> = args.value("--anonymize")?;
+    let source = Source::from_args(args, include_str!("../rsc/input.txt"))?;
+
+    let start = Instant::now();
+    let hands = match model::read(&source, PUZZLE, MODEL_VERSION)? {
+        Loaded::Model(hands) => hands,
+        Loaded::Text(input) => parse(&input)?,
+    };
+    if let Some(seed) = anonymize_seed {
+        print!("{}", anonymize(&hands, seed));
+        return Ok(());
+    }
+    println!("Input: {:.2?}\n", start.elapsed());
+
+    if let Some(path) = save_model {
+        model::save(&path, PUZZLE, MODEL_VERSION, &hands)?;
+        println!("Saved the model to {}", path.display());
+        return Ok(());
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&hands)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&hands)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    // A line of the input, like "32T3K 765".
+    fn hand_line() -> impl Strategy<Value = String> {
+        (
+            prop::array::uniform5(prop::sample::select(CARDS.chars().collect::<Vec<char>>())),
+            1..1000,
+        )
+            .prop_map(|(cards, bid)| format!("{} {}", String::from_iter(cards), bid))
+    }
+
+    // The hand with jacks, and with jokers.
+    fn parse_hands(line: &str) -> [Hand; 2] {
+        let hand = parse::parse(hand, line).unwrap();
+        let jokers = with_jokers(&hand);
+        [hand, jokers]
+    }
+
+    #[test]
+    fn answers_of_the_sample() {
+        let hands = parse(SAMPLE).unwrap();
+        assert_eq!(part1(&hands).unwrap(), 6440);
+        assert_eq!(part2(&hands).unwrap(), 5905);
+    }
+
+    #[test]
+    fn anonymizing_keeps_the_answers() {
+        let hands = parse(SAMPLE).unwrap();
+        for seed in 0..20 {
+            let anonymized = anonymize(&hands, seed);
+            let mut lines: Vec<&str> = anonymized.lines().collect();
+            lines.sort();
+            let mut original: Vec<&str> = SAMPLE.lines().collect();
+            original.sort();
+            assert_eq!(lines, original);
+
+            let anonymized = parse(&anonymized).unwrap();
+            assert_eq!(part1(&anonymized).unwrap(), 6440);
+            assert_eq!(part2(&anonymized).unwrap(), 5905);
+        }
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn overflow_is_an_error() {
+        // The second hand ranks 2, 2000000000 * 2 doesn't fit an i32.
+        let hands = parse("32T3K 684\nT55J5 2000000000\n").unwrap();
+        let error = part1(&hands).unwrap_err().to_string();
+        assert_eq!(error, "2023/day7 part 1: 2000000000 * 2 in i32 overflows");
+    }
+
+    proptest! {
+        #[test]
+        fn hand_order_is_total(a in hand_line(), b in hand_line(), c in hand_line()) {
+            for ((a, b), c) in parse_hands(&a).into_iter().zip(parse_hands(&b)).zip(parse_hands(&c)) {
+                prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+                // The bid doesn't count, just the cards.
+                prop_assert_eq!(a.cmp(&b) == Ordering::Equal, a.cards == b.cards);
+
+                let mut hands = [a, b, c];
+                hands.sort();
+                prop_assert!(hands[0] <= hands[1]);
+                prop_assert!(hands[1] <= hands[2]);
+                prop_assert!(hands[0] <= hands[2]);
+            }
+        }
+
+        #[test]
+        fn jokers_never_lower_strength(line in hand_line(), position in 0..5usize) {
+            let [jack, joker] = parse_hands(&line);
+            prop_assert!(joker.strength >= jack.strength);
+
+            // Neither does turning any card into a joker.
+            let mut cards = joker.cards;
+            cards[position] = 0;
+            prop_assert!(cards_to_strength(cards) >= joker.strength);
+        }
+    }
+}
@@ -0,0 +1,402 @@
+use aoc_common::anonymize::{Names, Rng};
+use aoc_common::assumptions::{self, Assumption, Check};
+use aoc_common::cli::{Args, UsageError};
+use aoc_common::graph::{Graph, NodeId};
+use aoc_common::parse::{
+    self, line_end, lines, pair, tag, take_while1, terminated, word, Input, ParseError, ParseResult,
+};
+use aoc_common::stream::{Source, StreamError};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::time::Instant;
+
+#[derive(Debug)]
+enum Error {
+    ParseError(ParseError),
+    UsageError(UsageError),
+    AssumptionFailed,
+    WalkError,
+    MissingNode,
+    MalformedInstruction,
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::UsageError(e) => write!(f, "{}", e),
+            Error::AssumptionFailed => {
+                write!(f, "the input doesn't hold the assumptions of the solution")
+            }
+            Error::WalkError => write!(f, "the instructions are empty"),
+            Error::MissingNode => write!(f, "a node is missing"),
+            Error::MalformedInstruction => write!(f, "an instruction is neither L nor R"),
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Each node has exactly two edges: the first one is "left", the second one is "right".
+fn step(nodes: &Graph<&str>, id: NodeId, direction: char) -> Result<NodeId, Error> {
+    let edges = nodes.edges(id);
+    if edges.len() != 2 {
+        return Err(Error::MissingNode);
+    }
+
+    match direction {
+        'L' => Ok(edges[0].to),
+        'R' => Ok(edges[1].to),
+        _ => Err(Error::MalformedInstruction),
+    }
+}
+
+fn walk(nodes: &Graph<&str>, instructions: &str) -> Result<usize, Error> {
+    let mut steps = 0;
+    let mut instructions = instructions.chars().cycle();
+    let mut id = nodes.id("AAA").ok_or(Error::MissingNode)?;
+    let target = nodes.id("ZZZ").ok_or(Error::MissingNode)?;
+
+    while id != target {
+        let direction = instructions.next().ok_or(Error::WalkError)?;
+        id = step(nodes, id, direction)?;
+        steps += 1;
+    }
+
+    return Ok(steps);
+}
+
+fn walk_ghost(nodes: &Graph<&str>, instructions: &str, start: NodeId) -> Result<usize, Error> {
+    let mut steps = 0;
+    let mut instructions = instructions.chars().cycle();
+    let mut id = start;
+
+    while !nodes.node(id).ends_with("Z") {
+        let direction = instructions.next().ok_or(Error::WalkError)?;
+        id = step(nodes, id, direction)?;
+        steps += 1;
+    }
+
+    return Ok(steps);
+}
+
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    return a * (b / gcd(a, b));
+}
+
+fn ghost_starts(nodes: &Graph<&str>) -> Vec<NodeId> {
+    nodes
+        .nodes()
+        .filter(|(_, name)| name.ends_with("A"))
+        .map(|(id, _)| id)
+        .collect()
+}
+
+// The LCM of the steps each ghost needs to reach a Z node only is the answer if every ghost then
+// goes round in a cycle of just that length, see `ASSUMPTIONS`.
+fn walk_ghosts(nodes: &Graph<&str>, instructions: &str) -> Result<usize, Error> {
+    let start_ids = ghost_starts(nodes);
+    let steps: Result<HashSet<usize>, Error> = start_ids
+        .iter()
+        .map(|start| walk_ghost(nodes, instructions, *start))
+        .collect();
+
+    println!("{:?}", steps);
+
+    let foo = steps?.iter().cloned().reduce(|acc, step| lcm(acc, step));
+    foo.ok_or(Error::WalkError)
+}
+
+// Walks from `start`, beginning with the instruction at `offset`, until a node ending in "Z" is
+// reached after at least one step. Returns the steps and that node, or `None` after `limit` steps.
+fn walk_to_z(
+    nodes: &Graph<&str>,
+    instructions: &str,
+    start: NodeId,
+    offset: usize,
+    limit: usize,
+) -> Result<Option<(usize, NodeId)>, Error> {
+    let directions: Vec<char> = instructions.chars().collect();
+    let mut id = start;
+
+    for steps in 1..=limit {
+        id = step(
+            nodes,
+            id,
+            directions[(offset + steps - 1) % directions.len()],
+        )?;
+        if nodes.node(id).ends_with("Z") {
+            return Ok(Some((steps, id)));
+        }
+    }
+
+    Ok(None)
+}
+
+// For every ghost, its start and where it reaches a Z node first: after how many steps, and which
+// one.
+fn first_z_nodes(input: &str) -> Result<Vec<(String, usize, NodeId)>, String> {
+    let (instructions, nodes) = parse(input).map_err(|e| e.to_string())?;
+    // Without reaching a Z node, a walk repeats itself after this many steps at the latest.
+    let limit = nodes.len() * instructions.len();
+    let mut result = Vec::new();
+
+    for start in ghost_starts(&nodes) {
+        let name = nodes.node(start).to_string();
+        match walk_to_z(&nodes, instructions, start, 0, limit).map_err(|e| e.to_string())? {
+            Some((steps, z)) => result.push((name, steps, z)),
+            None => return Err(format!("the ghost from {} never reaches a Z node", name)),
+        }
+    }
+
+    Ok(result)
+}
+
+fn check_ghosts_reach_z(input: &str) -> Check {
+    first_z_nodes(input).map(|_| ())
+}
+
+fn check_instructions_aligned(input: &str) -> Check {
+    let (instructions, _) = parse(input).map_err(|e| e.to_string())?;
+    for (name, steps, _) in first_z_nodes(input)? {
+        if steps % instructions.len() != 0 {
+            return Err(format!(
+                "the ghost from {} needs {} steps, with {} instructions",
+                name,
+                steps,
+                instructions.len()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn check_cycles_match_first_walk(input: &str) -> Check {
+    let (instructions, nodes) = parse(input).map_err(|e| e.to_string())?;
+    for (name, steps, z) in first_z_nodes(input)? {
+        let next = walk_to_z(&nodes, instructions, z, steps, steps).map_err(|e| e.to_string())?;
+        if next != Some((steps, z)) {
+            return Err(format!(
+                "the ghost from {} doesn't get back to {} after another {} steps",
+                name,
+                nodes.node(z),
+                steps
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+const ASSUMPTIONS: [Assumption<str>; 3] = [
+    Assumption {
+        description: "every ghost reaches a Z node",
+        check: check_ghosts_reach_z,
+    },
+    Assumption {
+        description: "the steps to the first Z node are a multiple of the instructions' length",
+        check: check_instructions_aligned,
+    },
+    Assumption {
+        description:
+            "from its first Z node, every ghost cycles back to it in the same number of steps",
+        check: check_cycles_match_first_walk,
+    },
+];
+
+// "AAA = (BBB, CCC)"
+fn node(input: Input<'_>) -> ParseResult<'_, (&str, (&str, &str))> {
+    pair(
+        terminated(word(), tag(" = (")),
+        pair(terminated(word(), tag(", ")), terminated(word(), tag(")"))),
+    )(input)
+}
+
+// The nodes with their left and right neighbours.
+type Nodes<'a> = Vec<(&'a str, (&'a str, &'a str))>;
+
+fn parse_lines(input: &str) -> Result<(&str, Nodes<'_>), Error> {
+    let instructions = take_while1("instructions", |c| c == 'L' || c == 'R');
+    let document = pair(
+        terminated(instructions, pair(line_end(), line_end())),
+        lines(node),
+    );
+    parse::parse(document, input).map_err(Error::ParseError)
+}
+
+fn parse(input: &str) -> Result<(&str, Graph<&str>), Error> {
+    let (instructions, lines) = parse_lines(input)?;
+
+    let mut nodes: Graph<&str> = Graph::directed();
+    for (name, (left, right)) in lines {
+        let id = nodes.intern(name);
+        let left = nodes.intern(left);
+        let right = nodes.intern(right);
+        nodes.add_edge(id, left, 1);
+        nodes.add_edge(id, right, 1);
+    }
+
+    Ok((instructions, nodes))
+}
+
+// Made-up names keep their last character, as the ghosts start on the nodes ending in A and stop on
+// those ending in Z. AAA and ZZZ stay as they are.
+fn made_up_name(rng: &mut Rng, name: &str) -> String {
+    let last = name.chars().last().unwrap_or('X');
+    format!(
+        "{}{}",
+        rng.pick("ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789", 2),
+        last
+    )
+}
+
+// The same network with other names, and the nodes in another order.
+fn anonymize(input: &str, seed: u64) -> Result<String, Error> {
+    let (instructions, mut nodes) = parse_lines(input)?;
+    let mut names = Names::new(seed);
+    names.keep("AAA");
+    names.keep("ZZZ");
+    names.rng().shuffle(&mut nodes);
+
+    let mut anonymized = format!("{}\n\n", instructions);
+    for (name, (left, right)) in nodes {
+        anonymized += &format!(
+            "{} = ({}, {})\n",
+            names.rename(name, made_up_name),
+            names.rename(left, made_up_name),
+            names.rename(right, made_up_name)
+        );
+    }
+    Ok(anonymized)
+}
+
+fn part1(input: &str) -> Result<usize, Error> {
+    let (instructions, nodes) = parse(input)?;
+    let steps = walk(&nodes, instructions)?;
+
+    return Ok(steps);
+}
+
+fn part2(input: &str) -> Result<usize, Error> {
+    let (instructions, nodes) = parse(input)?;
+    let steps = walk_ghosts(&nodes, instructions)?;
+
+    return Ok(steps);
+}
+
+fn main() -> Result<(), Error> {
+    let mut args = Args::from_env();
+    let check_assumptions = args.flag("--check-assumptions");
+    let anonymize_seed: Option<u64> = args.value("--anonymize").map_err(Error::UsageError)?;
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
+
+    if let Some(seed) = anonymize_seed {
+        print!("{}", anonymize(&input, seed)?);
+        return Ok(());
+    }
+
+    if check_assumptions {
+        if !assumptions::report(input.as_str(), &ASSUMPTIONS) {
+            return Err(Error::AssumptionFailed);
+        }
+        println!();
+    }
+
+    let start1 = Instant::now();
+    println!("Part 1: {}", part1(&input)?);
+    println!("Elapsed: {:.2?}\n", start1.elapsed());
+
+    let start2 = Instant::now();
+    println!("Part 2: {}", part2(&input)?);
+    println!("Elapsed: {:.2?}", start2.elapsed());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE1: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const SAMPLE2: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn answers_of_the_samples() {
+        assert_eq!(part1(SAMPLE1).unwrap(), 6);
+        assert_eq!(part2(SAMPLE2).unwrap(), 6);
+    }
+
+    // Which of the assumptions hold for the input, in their order.
+    fn holding(input: &str) -> Vec<bool> {
+        ASSUMPTIONS
+            .iter()
+            .map(|assumption| (assumption.check)(input).is_ok())
+            .collect()
+    }
+
+    #[test]
+    fn assumptions_on_crafted_inputs() {
+        // 22A needs 3 steps with 2 instructions, but then cycles back in 3 steps again.
+        assert_eq!(holding(SAMPLE2), [true, false, true]);
+
+        let aligned =
+            "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)\n";
+        assert_eq!(holding(aligned), [true, true, true]);
+
+        let lost = "L\n\n11A = (11B, 11B)\n11B = (11B, 11B)\n";
+        assert_eq!(holding(lost), [false, false, false]);
+        assert_eq!(
+            check_ghosts_reach_z(lost),
+            Err("the ghost from 11A never reaches a Z node".to_string())
+        );
+
+        // 11Z comes after 2 steps, but then after every single one.
+        let stuck = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11Z, 11Z)\n";
+        assert_eq!(holding(stuck), [true, true, false]);
+    }
+
+    #[test]
+    fn anonymizing_keeps_the_answers() {
+        for seed in 0..20 {
+            let anonymized = anonymize(SAMPLE1, seed).unwrap();
+            assert_eq!(part1(&anonymized).unwrap(), 6);
+            assert_eq!(anonymized, anonymize(SAMPLE1, seed).unwrap());
+
+            let anonymized = anonymize(SAMPLE2, seed).unwrap();
+            assert_eq!(part2(&anonymized).unwrap(), 6);
+            assert_ne!(anonymized, SAMPLE2);
+        }
+    }
+}
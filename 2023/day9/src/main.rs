@@ -0,0 +1,146 @@
+use aoc_common::checked::{Arith, Overflow};
+use aoc_common::parse::{integer, separated, space1, ParseError};
+use aoc_common::stream::{self, Options, StreamError};
+use itertools::Itertools;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::time::Instant;
+
+#[derive(Debug)]
+enum Error {
+    ParseError(ParseError),
+    StreamError(StreamError),
+    OutOfBounds,
+    Overflow(Overflow),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::ParseError(e) => write!(f, "{}", e),
+            Error::StreamError(e) => write!(f, "{}", e),
+            Error::OutOfBounds => write!(f, "a history is empty"),
+            Error::Overflow(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+const PART1: Arith = Arith::new("2023/day9", 1);
+const PART2: Arith = Arith::new("2023/day9", 2);
+
+// Function/closure that combines the list of numbers of a "level" with the difference passed from
+// previous level.
+type Processor = fn(&Vec<i32>, i32) -> Result<i32, Error>;
+
+// Parses the input, recurses on each line with the passed processor. The part's arithmetic is
+// checked for overflow with the `checked` feature.
+fn parse_and_process(
+    input: impl BufRead,
+    arith: Arith,
+    processor: Processor,
+) -> Result<i32, Error> {
+    let mut sum = 0;
+
+    for record in stream::lines(input) {
+        let record = record.map_err(Error::StreamError)?;
+        // Parse the line into a list of numbers.
+        let numbers = record
+            .parse(separated(integer(), space1()))
+            .map_err(Error::ParseError)?;
+        // Evaluate the line using the processor.
+        let next = recurse(&numbers, arith, processor)?;
+        sum = arith.add(sum, next).map_err(Error::Overflow)?;
+    }
+
+    Ok(sum)
+}
+
+// Evaluates a "level" by calculating the differences and passing it to a processor.
+// Returns the difference returned from the process, or 0 if the input is all zeros.
+fn recurse(numbers: &Vec<i32>, arith: Arith, processor: Processor) -> Result<i32, Error> {
+    let is_all_zeros = numbers.iter().find(|&&i| i != 0) == None;
+    if is_all_zeros {
+        return Ok(0);
+    }
+
+    // Using a sliding window, calculate the differences between succeeding numbers
+    // and gathers those deltas in a new vector.
+    let differences: Vec<i32> = numbers
+        .iter()
+        .tuple_windows::<(_, _)>()
+        .map(|t| arith.sub(*t.1, *t.0))
+        .collect::<Result<_, _>>()
+        .map_err(Error::Overflow)?;
+
+    // Recurse on these vector of deltas.
+    let diff = recurse(&differences, arith, processor)?;
+    // Feed the original "level" numbers and the difference returned from recursion
+    // to the processor.
+    processor(numbers, diff)
+}
+
+fn part1(input: impl BufRead) -> Result<i32, Error> {
+    let result = parse_and_process(input, PART1, |numbers, difference| {
+        let num = numbers.last().ok_or(Error::OutOfBounds)?;
+        PART1.add(*num, difference).map_err(Error::Overflow)
+    })?;
+    return Ok(result);
+}
+
+fn part2(input: impl BufRead) -> Result<i32, Error> {
+    let result = parse_and_process(input, PART2, |numbers, difference| {
+        let num = numbers.first().ok_or(Error::OutOfBounds)?;
+        PART2.sub(*num, difference).map_err(Error::Overflow)
+    })?;
+    return Ok(result);
+}
+
+fn main() -> Result<(), Error> {
+    let options =
+        Options::from_args(include_str!("../rsc/input.txt")).map_err(Error::StreamError)?;
+    let open = || options.source.open().map_err(Error::StreamError);
+
+    if options.runs(1) {
+        let start1 = Instant::now();
+        println!("Part 1: {}", part1(open()?)?);
+        println!("Elapsed: {:.2?}\n", start1.elapsed());
+    }
+
+    if options.runs(2) {
+        let start2 = Instant::now();
+        println!("Part 2: {}", part2(open()?)?);
+        println!("Elapsed: {:.2?}", start2.elapsed());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn answers_of_the_sample() {
+        assert_eq!(part1(SAMPLE.as_bytes()).unwrap(), 114);
+        assert_eq!(part2(SAMPLE.as_bytes()).unwrap(), 2);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn overflow_is_an_error() {
+        // The difference of -4000000000 doesn't fit an i32.
+        let error = part1("2000000000 -2000000000\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "2023/day9 part 1: -2000000000 - 2000000000 in i32 overflows"
+        );
+    }
+}
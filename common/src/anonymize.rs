@@ -0,0 +1,92 @@
+//! Puzzle inputs mustn't be shared, but a bug report is hard to reproduce without one. The days
+//! that can take `--anonymize SEED`: instead of solving the input, they print an equivalent one,
+//! with made-up names and shuffled lines, that has the same answers. The seed makes it repeatable.
+
+use std::collections::{HashMap, HashSet};
+
+/// SplitMix64. Good enough to make up names and shuffle lines, and it's the same everywhere.
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, `n` must not be 0. Slightly biased, which doesn't matter here.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Fisher-Yates.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    /// `len` characters picked from the alphabet.
+    pub fn pick(&mut self, alphabet: &str, len: usize) -> String {
+        let chars: Vec<char> = alphabet.chars().collect();
+        (0..len).map(|_| chars[self.below(chars.len())]).collect()
+    }
+}
+
+/// Renames consistently: a name gets the same new name every time, and no two names get the same.
+pub struct Names {
+    rng: Rng,
+    names: HashMap<String, String>,
+    taken: HashSet<String>,
+}
+
+impl Names {
+    pub fn new(seed: u64) -> Names {
+        Names {
+            rng: Rng::new(seed),
+            names: HashMap::new(),
+            taken: HashSet::new(),
+        }
+    }
+
+    /// Leaves the name as it is, for those the puzzle refers to (like "AAA"). Has to come before
+    /// any renaming, so no other name is renamed to it.
+    pub fn keep(&mut self, name: &str) {
+        self.names.insert(name.to_string(), name.to_string());
+        self.taken.insert(name.to_string());
+    }
+
+    /// The new name of `name`. The first time, `make_up` comes up with one (as often as needed for
+    /// one that isn't taken yet), given the original.
+    pub fn rename<F>(&mut self, name: &str, make_up: F) -> String
+    where
+        F: Fn(&mut Rng, &str) -> String,
+    {
+        if let Some(renamed) = self.names.get(name) {
+            return renamed.clone();
+        }
+
+        let renamed = loop {
+            let candidate = make_up(&mut self.rng, name);
+            if !self.taken.contains(&candidate) {
+                break candidate;
+            }
+        };
+        self.taken.insert(renamed.clone());
+        self.names.insert(name.to_string(), renamed.clone());
+        renamed
+    }
+
+    /// For shuffling with the same seed.
+    pub fn rng(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+}
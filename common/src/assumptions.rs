@@ -0,0 +1,29 @@
+//! Some solutions take shortcuts that only work because of properties of the puzzle input. The
+//! days state these properties as checks, so `--check-assumptions` can tell whether a given input
+//! has them before the shortcut is trusted.
+
+/// `Err` says why the assumption doesn't hold.
+pub type Check = Result<(), String>;
+
+pub struct Assumption<I: ?Sized> {
+    pub description: &'static str,
+    pub check: fn(&I) -> Check,
+}
+
+/// Checks every assumption against the input and prints which of them hold. Returns whether all
+/// of them do.
+pub fn report<I: ?Sized>(input: &I, assumptions: &[Assumption<I>]) -> bool {
+    let mut all_hold = true;
+
+    for assumption in assumptions {
+        match (assumption.check)(input) {
+            Ok(()) => println!("Holds: {}", assumption.description),
+            Err(reason) => {
+                println!("Does not hold: {} ({})", assumption.description, reason);
+                all_hold = false;
+            }
+        }
+    }
+
+    all_hold
+}
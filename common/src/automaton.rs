@@ -0,0 +1,240 @@
+//! Finding many words in a text at once, in a single pass (Aho-Corasick). The words are put into a
+//! trie whose nodes also know where to go on after a mismatch, so no byte of the text is looked at
+//! twice and overlapping words ("eightwo") are all found. A second automaton of the reversed words
+//! finds the last match by scanning from the end.
+
+use std::collections::VecDeque;
+
+/// A word found in a text: the bytes `start..end`, and the value the word stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<V> {
+    pub start: usize,
+    pub end: usize,
+    pub value: V,
+}
+
+impl<V> Match<V> {
+    // Whether it comes before the other match: it starts earlier, or is longer at the same start.
+    fn precedes(&self, other: &Match<V>) -> bool {
+        self.start < other.start || (self.start == other.start && self.end > other.end)
+    }
+}
+
+const ROOT: u32 = 0;
+const NONE: u32 = u32::MAX;
+
+// The automaton in one direction.
+struct Trie {
+    // The node to go to from each node on each byte. Mismatches are resolved when building, so
+    // scanning is a single lookup per byte.
+    next: Vec<[u32; 256]>,
+    // The words (as indices) that end at each node, including those that are a suffix of the path
+    // to it.
+    ends: Vec<Vec<usize>>,
+}
+
+impl Trie {
+    fn new<'a>(words: impl Iterator<Item = &'a [u8]>, reversed: bool) -> Trie {
+        let mut next = vec![[NONE; 256]];
+        let mut ends = vec![Vec::new()];
+        for (index, word) in words.enumerate() {
+            let mut node = ROOT;
+            let bytes: Vec<u8> = if reversed {
+                word.iter().rev().copied().collect()
+            } else {
+                word.to_vec()
+            };
+            for byte in bytes {
+                if next[node as usize][byte as usize] == NONE {
+                    next[node as usize][byte as usize] = next.len() as u32;
+                    next.push([NONE; 256]);
+                    ends.push(Vec::new());
+                }
+                node = next[node as usize][byte as usize];
+            }
+            ends[node as usize].push(index);
+        }
+
+        // Breadth first, so the node a mismatch falls back to (a shorter suffix) is complete by the
+        // time the nodes that fall back to it are.
+        let mut fallback = vec![ROOT; next.len()];
+        let mut queue = VecDeque::new();
+        for to in next[ROOT as usize].iter_mut() {
+            match *to {
+                NONE => *to = ROOT,
+                child => queue.push_back(child),
+            }
+        }
+        while let Some(node) = queue.pop_front() {
+            let back = fallback[node as usize];
+            let inherited = ends[back as usize].clone();
+            ends[node as usize].extend(inherited);
+
+            let on_mismatch = next[back as usize];
+            for (to, on_mismatch) in next[node as usize].iter_mut().zip(on_mismatch) {
+                match *to {
+                    NONE => *to = on_mismatch,
+                    child => {
+                        fallback[child as usize] = on_mismatch;
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+
+        Trie { next, ends }
+    }
+}
+
+/// Finds words, each standing for a value, in texts.
+pub struct Automaton<V> {
+    forward: Trie,
+    backward: Trie,
+    words: Vec<(usize, V)>,
+    longest: usize,
+}
+
+impl<V: Copy> Automaton<V> {
+    /// The words must not be empty.
+    pub fn new<W: AsRef<str>>(words: impl IntoIterator<Item = (W, V)>) -> Automaton<V> {
+        let (words, values): (Vec<W>, Vec<V>) = words.into_iter().unzip();
+        let bytes = || words.iter().map(|w| w.as_ref().as_bytes());
+        assert!(bytes().all(|w| !w.is_empty()), "empty words match anywhere");
+
+        Automaton {
+            forward: Trie::new(bytes(), false),
+            backward: Trie::new(bytes(), true),
+            words: bytes().map(|w| w.len()).zip(values).collect(),
+            longest: bytes().map(|w| w.len()).max().unwrap_or(0),
+        }
+    }
+
+    // The matches of the words ending at the node, which is at `position` in the direction it was
+    // found in.
+    fn ends_at<'a>(
+        &'a self,
+        trie: &'a Trie,
+        node: u32,
+        position: usize,
+        reversed: bool,
+    ) -> impl Iterator<Item = Match<V>> + 'a {
+        trie.ends[node as usize].iter().map(move |&word| {
+            let (len, value) = self.words[word];
+            if reversed {
+                Match {
+                    start: position,
+                    end: position + len,
+                    value,
+                }
+            } else {
+                Match {
+                    start: position + 1 - len,
+                    end: position + 1,
+                    value,
+                }
+            }
+        })
+    }
+
+    /// All matches, overlapping ones too, by where they start (and the longest first).
+    pub fn matches(&self, text: &str) -> Vec<Match<V>> {
+        let mut matches = Vec::new();
+        let mut node = ROOT;
+        for (i, byte) in text.bytes().enumerate() {
+            node = self.forward.next[node as usize][byte as usize];
+            matches.extend(self.ends_at(&self.forward, node, i, false));
+        }
+
+        matches.sort_by_key(|m| (m.start, usize::MAX - m.end));
+        matches
+    }
+
+    /// The match that starts first. Scanning stops as soon as no word could start before it.
+    pub fn first(&self, text: &str) -> Option<Match<V>> {
+        let mut first: Option<Match<V>> = None;
+        let mut node = ROOT;
+        for (i, byte) in text.bytes().enumerate() {
+            if first.is_some_and(|f| i >= f.start + self.longest) {
+                break;
+            }
+
+            node = self.forward.next[node as usize][byte as usize];
+            for found in self.ends_at(&self.forward, node, i, false) {
+                if first.is_none_or(|f| found.precedes(&f)) {
+                    first = Some(found);
+                }
+            }
+        }
+        first
+    }
+
+    /// The match that starts last, found by scanning from the end.
+    pub fn last(&self, text: &str) -> Option<Match<V>> {
+        let mut node = ROOT;
+        for (i, byte) in text.bytes().enumerate().rev() {
+            node = self.backward.next[node as usize][byte as usize];
+            let longest = self.ends_at(&self.backward, node, i, true).reduce(|a, b| {
+                if b.end > a.end {
+                    b
+                } else {
+                    a
+                }
+            });
+            if longest.is_some() {
+                return longest;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(matches: &[Match<u32>]) -> Vec<(usize, usize, u32)> {
+        matches.iter().map(|m| (m.start, m.end, m.value)).collect()
+    }
+
+    #[test]
+    fn overlapping_and_nested_words() {
+        let automaton =
+            Automaton::new([("one", 1), ("oneight", 18), ("eight", 8), ("eighteen", 81)]);
+        assert_eq!(
+            spans(&automaton.matches("xoneighteen")),
+            [(1, 8, 18), (1, 4, 1), (3, 11, 81), (3, 8, 8)]
+        );
+        let first = automaton.first("xoneighteen").unwrap();
+        assert_eq!((first.start, first.end, first.value), (1, 8, 18));
+        let last = automaton.last("xoneighteen").unwrap();
+        assert_eq!((last.start, last.end, last.value), (3, 11, 81));
+
+        // A word that is a suffix of another is found inside it.
+        let automaton = Automaton::new([("abcd", 1), ("bc", 2), ("c", 3)]);
+        assert_eq!(
+            spans(&automaton.matches("abcd")),
+            [(0, 4, 1), (1, 3, 2), (2, 3, 3)]
+        );
+    }
+
+    #[test]
+    fn no_matches() {
+        let automaton = Automaton::new([("one", 1), ("two", 2)]);
+        for text in ["", "on", "ttwwoo", "oen"] {
+            assert!(automaton.matches(text).is_empty());
+            assert_eq!(automaton.first(text), None);
+            assert_eq!(automaton.last(text), None);
+        }
+    }
+
+    #[test]
+    fn offsets_are_in_bytes() {
+        let automaton = Automaton::new([("ein", 1), ("zwölf", 12), ("drei", 3)]);
+        let text = "äzwölfeinß";
+        assert_eq!(spans(&automaton.matches(text)), [(2, 8, 12), (8, 11, 1)]);
+        let last = automaton.last(text).unwrap();
+        assert_eq!(&text[last.start..last.end], "ein");
+        let first = automaton.first(text).unwrap();
+        assert_eq!(&text[first.start..first.end], "zwölf");
+    }
+}
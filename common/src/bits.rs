@@ -0,0 +1,352 @@
+//! Sets of small indices and grid positions as bits, 64 per word. Visited and energized tiles, rocks
+//! and reached plots are asked about millions of times on a grid of at most a few hundred tiles a
+//! side, where hashing a position costs far more than testing a bit.
+
+use crate::vector::Vec2;
+
+const BITS: usize = u64::BITS as usize;
+
+fn words_for(len: usize) -> usize {
+    len.div_ceil(BITS)
+}
+
+/// The indices of the set bits of some words.
+pub struct Ones<'a> {
+    words: &'a [u64],
+    index: usize,
+    word: u64,
+}
+
+impl<'a> Ones<'a> {
+    fn new(words: &'a [u64]) -> Ones<'a> {
+        Ones {
+            words,
+            index: 0,
+            word: words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.index += 1;
+            self.word = *self.words.get(self.index)?;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.index * BITS + bit)
+    }
+}
+
+/// A set of the indices `0..len`. Indices out of that range panic.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> BitSet {
+        BitSet {
+            words: vec![0; words_for(len)],
+            len,
+        }
+    }
+
+    /// How many indices the set can hold, not how many it does (that's `count`).
+    pub fn capacity(&self) -> usize {
+        self.len
+    }
+
+    // Indices past `len` but within the last word would silently become members.
+    fn check(&self, i: usize) {
+        assert!(i < self.len, "{} is out of 0..{}", i, self.len);
+    }
+
+    /// Adds the index. True if it wasn't in the set yet, like `HashSet::insert`.
+    pub fn set(&mut self, i: usize) -> bool {
+        self.check(i);
+        let (word, mask) = (&mut self.words[i / BITS], 1 << (i % BITS));
+        let new = *word & mask == 0;
+        *word |= mask;
+        new
+    }
+
+    pub fn test(&self, i: usize) -> bool {
+        self.check(i);
+        self.words[i / BITS] & (1 << (i % BITS)) != 0
+    }
+
+    /// Removes the index. True if it was in the set.
+    pub fn clear(&mut self, i: usize) -> bool {
+        self.check(i);
+        let (word, mask) = (&mut self.words[i / BITS], 1 << (i % BITS));
+        let was = *word & mask != 0;
+        *word &= !mask;
+        was
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The number of indices in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Adds the indices of the other set, which must be of the same length.
+    pub fn union_with(&mut self, other: &BitSet) {
+        assert_eq!(self.len, other.len, "union of sets of different lengths");
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Keeps only the indices that are in the other set too, which must be of the same length.
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        assert_eq!(
+            self.len, other.len,
+            "intersection of sets of different lengths"
+        );
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+
+    pub fn union(&self, other: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitSet) -> BitSet {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    /// The indices in the set, in increasing order.
+    pub fn iter(&self) -> Ones<'_> {
+        Ones::new(&self.words)
+    }
+}
+
+/// A set of the positions on a `width` by `height` grid. Each row starts on a new word, so rows can
+/// be iterated on their own. Positions off the grid panic.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    row_words: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> BitGrid {
+        let row_words = words_for(width);
+        BitGrid {
+            words: vec![0; row_words * height],
+            width,
+            height,
+            row_words,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // An x past the width would set a bit in the padding of the row, or one in the next row.
+    fn locate(&self, pos: Vec2<usize>) -> (usize, u64) {
+        assert!(
+            pos.x < self.width && pos.y < self.height,
+            "{} is off the {}x{} grid",
+            pos,
+            self.width,
+            self.height
+        );
+        (pos.y * self.row_words + pos.x / BITS, 1 << (pos.x % BITS))
+    }
+
+    /// Adds the position. True if it wasn't in the set yet, like `HashSet::insert`.
+    pub fn set(&mut self, pos: Vec2<usize>) -> bool {
+        let (index, mask) = self.locate(pos);
+        let new = self.words[index] & mask == 0;
+        self.words[index] |= mask;
+        new
+    }
+
+    pub fn test(&self, pos: Vec2<usize>) -> bool {
+        let (index, mask) = self.locate(pos);
+        self.words[index] & mask != 0
+    }
+
+    /// Removes the position. True if it was in the set.
+    pub fn clear(&mut self, pos: Vec2<usize>) -> bool {
+        let (index, mask) = self.locate(pos);
+        let was = self.words[index] & mask != 0;
+        self.words[index] &= !mask;
+        was
+    }
+
+    pub fn clear_all(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// The number of positions in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    fn assert_same_size(&self, other: &BitGrid) {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "grids of different sizes: {}x{} and {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+    }
+
+    /// Adds the positions of the other grid, which must be of the same size.
+    pub fn union_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+    }
+
+    /// Keeps only the positions that are in the other grid too, which must be of the same size.
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        self.assert_same_size(other);
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word &= other;
+        }
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    /// The x of the positions in the set on row `y`, from left to right.
+    pub fn row(&self, y: usize) -> Ones<'_> {
+        let start = y * self.row_words;
+        Ones::new(&self.words[start..start + self.row_words])
+    }
+
+    /// The positions in the set, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = Vec2<usize>> + '_ {
+        (0..self.height).flat_map(move |y| self.row(y).map(move |x| Vec2::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ones_across_words() {
+        let words = [1 | 1 << 63, 0, 0, 1 << 5];
+        let ones: Vec<usize> = Ones::new(&words).collect();
+        assert_eq!(ones, [0, 63, 3 * 64 + 5]);
+        assert_eq!(Ones::new(&[]).count(), 0);
+        assert_eq!(Ones::new(&[0, 0]).count(), 0);
+    }
+
+    #[test]
+    fn sets() {
+        let mut set = BitSet::new(130);
+        assert!(set.is_empty());
+        assert!(set.set(63));
+        assert!(set.set(64));
+        assert!(set.set(129));
+        assert!(!set.set(64));
+        assert!(set.test(129) && !set.test(128));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [63, 64, 129]);
+        assert!(set.clear(63));
+        assert!(!set.clear(63));
+        assert_eq!(set.count(), 2);
+
+        let mut other = BitSet::new(130);
+        other.set(0);
+        other.set(129);
+        assert_eq!(set.union(&other).iter().collect::<Vec<_>>(), [0, 64, 129]);
+        assert_eq!(set.intersection(&other).iter().collect::<Vec<_>>(), [129]);
+    }
+
+    #[test]
+    #[should_panic(expected = "130 is out of 0..130")]
+    fn sets_check_their_range() {
+        BitSet::new(130).set(130);
+    }
+
+    #[test]
+    fn grids() {
+        // 70 wide, so rows take two words and the second one is mostly padding.
+        let mut grid = BitGrid::new(70, 3);
+        for pos in [(69, 0), (0, 1), (64, 1), (63, 2)] {
+            assert!(grid.set(Vec2::new(pos.0, pos.1)));
+        }
+        assert!(!grid.set(Vec2::new(64, 1)));
+        assert_eq!(grid.row(0).collect::<Vec<_>>(), [69]);
+        assert_eq!(grid.row(1).collect::<Vec<_>>(), [0, 64]);
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            [
+                Vec2::new(69, 0),
+                Vec2::new(0, 1),
+                Vec2::new(64, 1),
+                Vec2::new(63, 2)
+            ]
+        );
+
+        let mut other = BitGrid::new(70, 3);
+        other.set(Vec2::new(69, 0));
+        other.set(Vec2::new(1, 2));
+        assert_eq!(grid.union(&other).count(), 5);
+        assert_eq!(
+            grid.intersection(&other).iter().collect::<Vec<_>>(),
+            [Vec2::new(69, 0)]
+        );
+        assert!(grid.clear(Vec2::new(69, 0)));
+        assert!(grid.row(0).next().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "is off the 70x3 grid")]
+    fn grids_check_the_width() {
+        // Would be a bit of the padding of row 0.
+        BitGrid::new(70, 3).set(Vec2::new(70, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "is off the 70x3 grid")]
+    fn grids_check_the_height() {
+        BitGrid::new(70, 3).test(Vec2::new(0, 3));
+    }
+}
@@ -0,0 +1,194 @@
+//! The integer types of the answers were picked without much thought, and an overflow in a release
+//! build silently gives a wrong answer. The arithmetic of the answers goes through `Arith`: with the
+//! `checked` feature, an overflow becomes an `Overflow` error naming the puzzle, the part and the
+//! operation. Without it, `Arith` does the plain operations, as fast as before.
+
+use std::fmt::Display;
+use std::ops::{Add, Mul, Shl, Shr, Sub};
+
+/// Integer types `Arith` can compute with.
+pub trait Integer:
+    Copy
+    + Display
+    + PartialEq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    const NAME: &'static str;
+    const ZERO: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_shl(self, rhs: u32) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const NAME: &'static str = stringify!($t);
+                const ZERO: Self = 0;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, rhs)
+                }
+
+                fn checked_shl(self, rhs: u32) -> Option<Self> {
+                    <$t>::checked_shl(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug)]
+pub struct Overflow {
+    pub puzzle: &'static str,
+    pub part: u32,
+    /// Like "765 * 1000 in i32".
+    pub operation: String,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} part {}: {} overflows",
+            self.puzzle, self.part, self.operation
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// The arithmetic of one part of a puzzle (like "2023/day7"), checked with the `checked` feature.
+#[derive(Clone, Copy)]
+pub struct Arith {
+    puzzle: &'static str,
+    part: u32,
+}
+
+impl Arith {
+    pub const fn new(puzzle: &'static str, part: u32) -> Arith {
+        Arith { puzzle, part }
+    }
+
+    fn overflow<T: Integer>(&self, a: T, operator: &str, b: impl Display) -> Overflow {
+        Overflow {
+            puzzle: self.puzzle,
+            part: self.part,
+            operation: format!("{} {} {} in {}", a, operator, b, T::NAME),
+        }
+    }
+
+    pub fn add<T: Integer>(&self, a: T, b: T) -> Result<T, Overflow> {
+        if cfg!(feature = "checked") {
+            a.checked_add(b).ok_or_else(|| self.overflow(a, "+", b))
+        } else {
+            Ok(a + b)
+        }
+    }
+
+    pub fn sub<T: Integer>(&self, a: T, b: T) -> Result<T, Overflow> {
+        if cfg!(feature = "checked") {
+            a.checked_sub(b).ok_or_else(|| self.overflow(a, "-", b))
+        } else {
+            Ok(a - b)
+        }
+    }
+
+    pub fn mul<T: Integer>(&self, a: T, b: T) -> Result<T, Overflow> {
+        if cfg!(feature = "checked") {
+            a.checked_mul(b).ok_or_else(|| self.overflow(a, "*", b))
+        } else {
+            Ok(a * b)
+        }
+    }
+
+    /// Overflows when bits are shifted out, not just when shifting by the width of the type or more.
+    pub fn shl<T: Integer>(&self, a: T, shift: u32) -> Result<T, Overflow> {
+        if cfg!(feature = "checked") {
+            a.checked_shl(shift)
+                .filter(|shifted| *shifted >> shift == a)
+                .ok_or_else(|| self.overflow(a, "<<", shift))
+        } else {
+            Ok(a << shift)
+        }
+    }
+
+    pub fn sum<T: Integer>(&self, values: impl IntoIterator<Item = T>) -> Result<T, Overflow> {
+        values
+            .into_iter()
+            .try_fold(T::ZERO, |sum, value| self.add(sum, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARITH: Arith = Arith::new("2023/day1", 2);
+
+    #[test]
+    fn results_that_fit() {
+        assert_eq!(ARITH.add(2, 3).unwrap(), 5);
+        assert_eq!(ARITH.sub(2i32, 3).unwrap(), -1);
+        assert_eq!(ARITH.mul(6u8, 7).unwrap(), 42);
+        assert_eq!(ARITH.shl(1u32, 31).unwrap(), 1 << 31);
+        assert_eq!(ARITH.sum([1u64, 2, 3]).unwrap(), 6);
+        assert_eq!(ARITH.sum(Vec::<usize>::new()).unwrap(), 0);
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn overflows_are_errors() {
+        let message = |result: Result<u8, Overflow>| result.unwrap_err().to_string();
+        assert_eq!(
+            message(ARITH.add(200, 100)),
+            "2023/day1 part 2: 200 + 100 in u8 overflows"
+        );
+        assert_eq!(
+            message(ARITH.sub(1, 2)),
+            "2023/day1 part 2: 1 - 2 in u8 overflows"
+        );
+        assert_eq!(
+            message(ARITH.mul(16, 16)),
+            "2023/day1 part 2: 16 * 16 in u8 overflows"
+        );
+        assert_eq!(
+            message(ARITH.sum([100, 100, 100])),
+            "2023/day1 part 2: 200 + 100 in u8 overflows"
+        );
+
+        let error = ARITH.mul(i32::MIN, -1).unwrap_err();
+        assert_eq!(error.puzzle, "2023/day1");
+        assert_eq!(error.part, 2);
+        assert_eq!(error.operation, "-2147483648 * -1 in i32");
+    }
+
+    #[cfg(feature = "checked")]
+    #[test]
+    fn shifting_bits_out_overflows() {
+        assert_eq!(ARITH.shl(0b0100_0000u8, 1).unwrap(), 0b1000_0000);
+        assert_eq!(
+            ARITH.shl(0b0100_0000u8, 2).unwrap_err().operation,
+            "64 << 2 in u8"
+        );
+        assert_eq!(ARITH.shl(1u32, 32).unwrap_err().operation, "1 << 32 in u32");
+        assert_eq!(ARITH.shl(-1i8, 7).unwrap(), i8::MIN);
+    }
+}
@@ -0,0 +1,78 @@
+//! Minimal command line handling for the days. Every option takes its arguments out of `Args`,
+//! whatever is left in the end are positional arguments.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+/// The command line arguments that haven't been taken by an option yet.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    /// The arguments of this process, without the program name.
+    pub fn from_env() -> Args {
+        Args::new(std::env::args().skip(1).collect())
+    }
+
+    pub fn new(args: Vec<String>) -> Args {
+        Args { args }
+    }
+
+    /// Takes the option `name` without a value, returns whether it was given.
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.args.iter().position(|a| a == name) {
+            Some(index) => {
+                self.args.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Takes the option `name` and the value following it.
+    pub fn value<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, UsageError> {
+        let Some(index) = self.args.iter().position(|a| a == name) else {
+            return Ok(None);
+        };
+        if index + 1 == self.args.len() {
+            return Err(UsageError(format!("{} needs a value", name)));
+        }
+
+        let value = self.args.remove(index + 1);
+        self.args.remove(index);
+        match value.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(_) => Err(UsageError(format!("invalid value for {}: {}", name, value))),
+        }
+    }
+
+    /// The remaining arguments, which must not look like options (except for `-`, which usually
+    /// means stdin).
+    pub fn positional(self) -> Result<Vec<String>, UsageError> {
+        if let Some(option) = self.args.iter().find(|a| a.starts_with('-') && *a != "-") {
+            return Err(UsageError(format!("unknown option {}", option)));
+        }
+
+        Ok(self.args)
+    }
+
+    /// Checks that all arguments have been taken by an option.
+    pub fn finish(self) -> Result<(), UsageError> {
+        match self.positional()?.first() {
+            Some(arg) => Err(UsageError(format!("unexpected argument {}", arg))),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,500 @@
+use std::{
+    borrow::Borrow,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+/// Index of an interned node. Ids are handed out in insertion order, starting at 0.
+pub type NodeId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    pub to: NodeId,
+    pub weight: usize,
+}
+
+/// A found path, including start and goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+    pub nodes: Vec<NodeId>,
+    pub cost: usize,
+}
+
+/// Graph with interned nodes and adjacency lists. Edges are kept in insertion order, so the n-th
+/// edge added for a node is also its n-th entry in `edges`.
+#[derive(Debug, Clone)]
+pub struct Graph<N> {
+    directed: bool,
+    nodes: Vec<N>,
+    ids: HashMap<N, NodeId>,
+    outgoing: Vec<Vec<Edge>>,
+    // For each node, the edges pointing to it. `Edge::to` is the source here.
+    incoming: Vec<Vec<Edge>>,
+}
+
+impl<N> Graph<N>
+where
+    N: Clone + Eq + Hash,
+{
+    pub fn directed() -> Graph<N> {
+        Graph {
+            directed: true,
+            nodes: Vec::new(),
+            ids: HashMap::new(),
+            outgoing: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+
+    /// Every edge added to an undirected graph is added in both directions.
+    pub fn undirected() -> Graph<N> {
+        Graph {
+            directed: false,
+            ..Graph::directed()
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the id of the node, adding it if it's not known yet.
+    pub fn intern(&mut self, node: N) -> NodeId {
+        if let Some(id) = self.ids.get(&node) {
+            return *id;
+        }
+
+        let id = self.nodes.len();
+        self.ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.outgoing.push(Vec::new());
+        self.incoming.push(Vec::new());
+        id
+    }
+
+    pub fn id<Q>(&self, node: &Q) -> Option<NodeId>
+    where
+        N: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(node).copied()
+    }
+
+    pub fn node(&self, id: NodeId) -> &N {
+        &self.nodes[id]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &N)> {
+        self.nodes.iter().enumerate()
+    }
+
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: usize) {
+        self.outgoing[from].push(Edge { to, weight });
+        self.incoming[to].push(Edge { to: from, weight });
+
+        if !self.directed && from != to {
+            self.outgoing[to].push(Edge { to: from, weight });
+            self.incoming[from].push(Edge { to, weight });
+        }
+    }
+
+    /// Removes the first edge from `from` to `to`. Returns whether there was such an edge.
+    pub fn remove_edge(&mut self, from: NodeId, to: NodeId) -> bool {
+        if !Self::remove_first(&mut self.outgoing[from], to) {
+            return false;
+        }
+        Self::remove_first(&mut self.incoming[to], from);
+
+        if !self.directed && from != to {
+            Self::remove_first(&mut self.outgoing[to], from);
+            Self::remove_first(&mut self.incoming[from], to);
+        }
+
+        true
+    }
+
+    fn remove_first(edges: &mut Vec<Edge>, to: NodeId) -> bool {
+        if let Some(index) = edges.iter().position(|e| e.to == to) {
+            edges.remove(index);
+            return true;
+        }
+        false
+    }
+
+    pub fn edges(&self, id: NodeId) -> &[Edge] {
+        &self.outgoing[id]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.outgoing[id].iter().map(|e| e.to)
+    }
+
+    pub fn predecessors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.incoming[id].iter().map(|e| e.to)
+    }
+
+    /// All edges as `(from, to, weight)`. In undirected graphs, each edge is only returned once,
+    /// with `from <= to`.
+    pub fn edge_list(&self) -> Vec<(NodeId, NodeId, usize)> {
+        let mut result = Vec::new();
+        for (from, edges) in self.outgoing.iter().enumerate() {
+            for edge in edges {
+                if self.directed || from <= edge.to {
+                    result.push((from, edge.to, edge.weight));
+                }
+            }
+        }
+        result
+    }
+
+    /// Breadth-first traversal, yielding each reachable node together with its depth.
+    pub fn bfs(&self, start: NodeId) -> Bfs<'_, N> {
+        let mut seen = vec![false; self.len()];
+        seen[start] = true;
+        Bfs {
+            graph: self,
+            seen,
+            queue: VecDeque::from([(start, 0)]),
+        }
+    }
+
+    /// Depth-first traversal, yielding each reachable node in pre-order.
+    pub fn dfs(&self, start: NodeId) -> Dfs<'_, N> {
+        Dfs {
+            graph: self,
+            seen: vec![false; self.len()],
+            stack: vec![start],
+        }
+    }
+
+    /// Groups of nodes that are connected to each other. Edge directions are ignored, so for
+    /// directed graphs these are the weakly connected components.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut component_of: Vec<Option<usize>> = vec![None; self.len()];
+        let mut components = Vec::new();
+
+        for start in 0..self.len() {
+            if component_of[start].is_some() {
+                continue;
+            }
+
+            let index = components.len();
+            let mut component = Vec::new();
+            let mut next = vec![start];
+            component_of[start] = Some(index);
+
+            while let Some(id) = next.pop() {
+                component.push(id);
+                for other in self.neighbours(id).chain(self.predecessors(id)) {
+                    if component_of[other].is_none() {
+                        component_of[other] = Some(index);
+                        next.push(other);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Cheapest path from `start` to `goal`.
+    pub fn dijkstra(&self, start: NodeId, goal: NodeId) -> Option<Path> {
+        self.a_star(start, goal, |_| 0)
+    }
+
+    /// Cheapest path from `start` to `goal`. The heuristic must never overestimate the remaining
+    /// cost to the goal, otherwise the found path is not guaranteed to be the cheapest.
+    pub fn a_star<F>(&self, start: NodeId, goal: NodeId, heuristic: F) -> Option<Path>
+    where
+        F: Fn(NodeId) -> usize,
+    {
+        let mut g_score = vec![usize::MAX; self.len()];
+        let mut came_from: Vec<Option<NodeId>> = vec![None; self.len()];
+        let mut open_set = BinaryHeap::new();
+
+        g_score[start] = 0;
+        open_set.push(Reverse((heuristic(start), start)));
+
+        while let Some(Reverse((f_score, current))) = open_set.pop() {
+            if current == goal {
+                let mut nodes = vec![goal];
+                let mut id = goal;
+                while let Some(previous) = came_from[id] {
+                    nodes.push(previous);
+                    id = previous;
+                }
+                nodes.reverse();
+
+                return Some(Path {
+                    nodes,
+                    cost: g_score[goal],
+                });
+            }
+
+            let current_g_score = g_score[current];
+            if f_score > current_g_score.saturating_add(heuristic(current)) {
+                // Stale heap entry, a cheaper way to this node was found in the meantime.
+                continue;
+            }
+
+            for edge in self.edges(current) {
+                let tentative_g_score = current_g_score + edge.weight;
+                if tentative_g_score < g_score[edge.to] {
+                    g_score[edge.to] = tentative_g_score;
+                    came_from[edge.to] = Some(current);
+                    open_set.push(Reverse((tentative_g_score + heuristic(edge.to), edge.to)));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Renders the graph in Graphviz DOT format. Edge weights other than 1 are used as labels.
+    pub fn to_dot(&self) -> String
+    where
+        N: Display,
+    {
+        let (keyword, connector) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut dot = String::new();
+        // Writing into a String can't fail.
+        _ = writeln!(dot, "{} {{", keyword);
+        for (id, node) in self.nodes() {
+            _ = writeln!(dot, "  n{} [label=\"{}\"];", id, escape(&node.to_string()));
+        }
+        for (from, to, weight) in self.edge_list() {
+            if weight == 1 {
+                _ = writeln!(dot, "  n{} {} n{};", from, connector, to);
+            } else {
+                _ = writeln!(
+                    dot,
+                    "  n{} {} n{} [label=\"{}\"];",
+                    from, connector, to, weight
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub struct Bfs<'a, N> {
+    graph: &'a Graph<N>,
+    seen: Vec<bool>,
+    queue: VecDeque<(NodeId, usize)>,
+}
+
+impl<N> Iterator for Bfs<'_, N>
+where
+    N: Clone + Eq + Hash,
+{
+    type Item = (NodeId, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (id, depth) = self.queue.pop_front()?;
+        for next in self.graph.neighbours(id) {
+            if !self.seen[next] {
+                self.seen[next] = true;
+                self.queue.push_back((next, depth + 1));
+            }
+        }
+        Some((id, depth))
+    }
+}
+
+pub struct Dfs<'a, N> {
+    graph: &'a Graph<N>,
+    seen: Vec<bool>,
+    stack: Vec<NodeId>,
+}
+
+impl<N> Iterator for Dfs<'_, N>
+where
+    N: Clone + Eq + Hash,
+{
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(id) = self.stack.pop() {
+            if self.seen[id] {
+                continue;
+            }
+            self.seen[id] = true;
+
+            // Push in reverse so the first neighbour is visited first.
+            let unseen: Vec<NodeId> = self.graph.neighbours(id).collect();
+            for next in unseen.into_iter().rev() {
+                if !self.seen[next] {
+                    self.stack.push(next);
+                }
+            }
+            return Some(id);
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a - b - c, and d on its own.
+    fn line(graph: &mut Graph<&'static str>) -> [NodeId; 4] {
+        let ids = ["a", "b", "c", "d"].map(|n| graph.intern(n));
+        graph.add_edge(ids[0], ids[1], 1);
+        graph.add_edge(ids[1], ids[2], 5);
+        ids
+    }
+
+    #[test]
+    fn undirected_edges_go_both_ways() {
+        let mut graph = Graph::undirected();
+        let [a, b, c, _] = line(&mut graph);
+        assert_eq!(graph.intern("b"), b);
+        assert_eq!(graph.neighbours(b).collect::<Vec<_>>(), [a, c]);
+        assert_eq!(graph.predecessors(a).collect::<Vec<_>>(), [b]);
+
+        assert!(graph.remove_edge(c, b));
+        assert!(!graph.remove_edge(b, c));
+        assert_eq!(graph.neighbours(b).collect::<Vec<_>>(), [a]);
+        assert_eq!(graph.predecessors(c).count(), 0);
+    }
+
+    #[test]
+    fn self_loops_are_added_once() {
+        let mut graph = Graph::undirected();
+        let a = graph.intern("a");
+        graph.add_edge(a, a, 2);
+        assert_eq!(graph.edges(a), [Edge { to: a, weight: 2 }]);
+        assert_eq!(graph.predecessors(a).collect::<Vec<_>>(), [a]);
+        assert_eq!(graph.edge_list(), [(a, a, 2)]);
+
+        assert!(graph.remove_edge(a, a));
+        assert!(graph.edges(a).is_empty());
+        assert_eq!(graph.predecessors(a).count(), 0);
+    }
+
+    #[test]
+    fn edge_lists() {
+        let mut undirected = Graph::undirected();
+        let [a, b, c, _] = line(&mut undirected);
+        assert_eq!(undirected.edge_list(), [(a, b, 1), (b, c, 5)]);
+
+        let mut directed = Graph::directed();
+        let [a, b, c, _] = line(&mut directed);
+        directed.add_edge(b, a, 1);
+        assert_eq!(directed.edge_list(), [(a, b, 1), (b, c, 5), (b, a, 1)]);
+    }
+
+    #[test]
+    fn components_ignore_directions() {
+        let mut graph = Graph::directed();
+        let [a, b, c, d] = line(&mut graph);
+        let e = graph.intern("e");
+        // Only reachable against the direction of the edge.
+        graph.add_edge(e, d, 1);
+
+        let mut components = graph.connected_components();
+        for component in &mut components {
+            component.sort();
+        }
+        assert_eq!(components, [vec![a, b, c], vec![d, e]]);
+    }
+
+    #[test]
+    fn cheapest_paths() {
+        let mut graph = Graph::directed();
+        let [a, _, c, d] = line(&mut graph);
+        graph.add_edge(a, d, 2);
+        graph.add_edge(d, c, 2);
+
+        let path = Path {
+            nodes: vec![a, d, c],
+            cost: 4,
+        };
+        assert_eq!(graph.dijkstra(a, c), Some(path.clone()));
+        assert_eq!(
+            graph.a_star(a, c, |id| if id == c { 0 } else { 1 }),
+            Some(path)
+        );
+        assert_eq!(
+            graph.dijkstra(a, a),
+            Some(Path {
+                nodes: vec![a],
+                cost: 0
+            })
+        );
+        assert_eq!(graph.dijkstra(c, a), None);
+    }
+
+    // a -> b -> d and a -> c -> d, with d leading back to a.
+    fn diamond() -> (Graph<&'static str>, [NodeId; 5]) {
+        let mut graph = Graph::directed();
+        let ids = ["a", "b", "c", "d", "e"].map(|n| graph.intern(n));
+        let [a, b, c, d, _] = ids;
+        for (from, to) in [(a, b), (a, c), (b, d), (c, d), (d, a)] {
+            graph.add_edge(from, to, 1);
+        }
+        (graph, ids)
+    }
+
+    #[test]
+    fn bfs_yields_depths() {
+        let (graph, [a, b, c, d, e]) = diamond();
+        assert_eq!(
+            graph.bfs(a).collect::<Vec<_>>(),
+            [(a, 0), (b, 1), (c, 1), (d, 2)]
+        );
+        assert_eq!(
+            graph.bfs(d).collect::<Vec<_>>(),
+            [(d, 0), (a, 1), (b, 2), (c, 2)]
+        );
+        assert_eq!(graph.bfs(e).collect::<Vec<_>>(), [(e, 0)]);
+    }
+
+    #[test]
+    fn dfs_visits_in_pre_order() {
+        let (graph, [a, b, c, d, e]) = diamond();
+        assert_eq!(graph.dfs(a).collect::<Vec<_>>(), [a, b, d, c]);
+        assert_eq!(graph.dfs(c).collect::<Vec<_>>(), [c, d, a, b]);
+        assert_eq!(graph.dfs(e).collect::<Vec<_>>(), [e]);
+    }
+
+    #[test]
+    fn dot_output() {
+        let mut graph = Graph::undirected();
+        let a = graph.intern("a");
+        let b = graph.intern("say \"b\"");
+        graph.add_edge(a, b, 1);
+        graph.add_edge(b, b, 3);
+        assert_eq!(
+            graph.to_dot(),
+            "graph {\n  n0 [label=\"a\"];\n  n1 [label=\"say \\\"b\\\"\"];\n  n0 -- n1;\n  n1 -- n1 [label=\"3\"];\n}\n"
+        );
+
+        let (directed, _) = diamond();
+        assert!(directed.to_dot().starts_with("digraph {\n"));
+        assert!(directed.to_dot().contains("  n3 -> n0;\n"));
+    }
+}
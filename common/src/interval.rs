@@ -0,0 +1,404 @@
+use std::ops::{Add, Mul, Range, Sub};
+
+/// Half-open interval `[start, end)`. An interval with `start >= end` is empty; operations may
+/// return empty intervals, check with `is_empty`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "model", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Ord,
+{
+    pub fn new(start: T, end: T) -> Interval<T> {
+        Interval { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        value >= self.start && value < self.end
+    }
+
+    pub fn intersection(&self, other: &Interval<T>) -> Interval<T> {
+        Interval {
+            start: self.start.max(other.start),
+            end: self.end.min(other.end),
+        }
+    }
+
+    /// The parts of `self` below and above `other`.
+    pub fn difference(&self, other: &Interval<T>) -> (Interval<T>, Interval<T>) {
+        if other.is_empty() {
+            return (*self, Interval::new(self.end, self.end));
+        }
+
+        let (below, rest) = self.split_at(other.start);
+        let (_, above) = rest.split_at(other.end);
+        (below, above)
+    }
+
+    /// Splits into `[start, at)` and `[at, end)`.
+    pub fn split_at(&self, at: T) -> (Interval<T>, Interval<T>) {
+        let at = at.max(self.start).min(self.end.max(self.start));
+        (Interval::new(self.start, at), Interval::new(at, self.end))
+    }
+}
+
+impl<T> Interval<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Default,
+{
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn shift(&self, delta: T) -> Interval<T> {
+        Interval::new(self.start + delta, self.end + delta)
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Interval {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+/// Set of values, stored as sorted, disjoint and non-adjacent intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals.iter().any(|i| i.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // Everything overlapping or touching the new interval is merged into it.
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let mut merged = interval;
+        if first < last {
+            merged.start = merged.start.min(self.intervals[first].start);
+            merged.end = merged.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        for interval in &other.intervals {
+            result.insert(*interval);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = IntervalSet::new();
+        for a in &self.intervals {
+            for b in &other.intervals {
+                result.insert(a.intersection(b));
+            }
+        }
+        result
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut remaining = self.intervals.clone();
+        for b in &other.intervals {
+            remaining = remaining
+                .iter()
+                .flat_map(|a| {
+                    let (below, above) = a.difference(b);
+                    [below, above]
+                })
+                .filter(|i| !i.is_empty())
+                .collect();
+        }
+        remaining.into_iter().collect()
+    }
+
+    /// Splits into the values below `at` and the values from `at` on.
+    pub fn split_at(&self, at: T) -> (IntervalSet<T>, IntervalSet<T>) {
+        let mut below = IntervalSet::new();
+        let mut above = IntervalSet::new();
+        for interval in &self.intervals {
+            let (b, a) = interval.split_at(at);
+            below.insert(b);
+            above.insert(a);
+        }
+        (below, above)
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Default,
+{
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::default(), |acc, i| acc + i.len())
+    }
+
+    pub fn shift(&self, delta: T) -> IntervalSet<T> {
+        IntervalSet {
+            intervals: self.intervals.iter().map(|i| i.shift(delta)).collect(),
+        }
+    }
+}
+
+impl<T> FromIterator<Interval<T>> for IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl<T> From<Interval<T>> for IntervalSet<T>
+where
+    T: Copy + Ord,
+{
+    fn from(interval: Interval<T>) -> Self {
+        [interval].into_iter().collect()
+    }
+}
+
+/// Axis-aligned box with one interval per dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntervalBox<T, const D: usize> {
+    pub axes: [Interval<T>; D],
+}
+
+impl<T, const D: usize> IntervalBox<T, D>
+where
+    T: Copy + Ord,
+{
+    pub fn new(axes: [Interval<T>; D]) -> IntervalBox<T, D> {
+        IntervalBox { axes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(|i| i.is_empty())
+    }
+
+    pub fn contains(&self, point: [T; D]) -> bool {
+        self.axes.iter().zip(point).all(|(i, v)| i.contains(v))
+    }
+
+    pub fn intersection(&self, other: &IntervalBox<T, D>) -> IntervalBox<T, D> {
+        let mut axes = self.axes;
+        for (axis, interval) in axes.iter_mut().enumerate() {
+            *interval = interval.intersection(&other.axes[axis]);
+        }
+        IntervalBox { axes }
+    }
+
+    /// Splits along one axis into the box below `at` and the box from `at` on.
+    pub fn split_at(&self, axis: usize, at: T) -> (IntervalBox<T, D>, IntervalBox<T, D>) {
+        let (below, above) = self.axes[axis].split_at(at);
+        let mut lower = *self;
+        let mut upper = *self;
+        lower.axes[axis] = below;
+        upper.axes[axis] = above;
+        (lower, upper)
+    }
+}
+
+impl<T, const D: usize> IntervalBox<T, D>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + Default,
+{
+    /// Number of points in the box.
+    pub fn volume(&self) -> T {
+        if self.is_empty() {
+            return T::default();
+        }
+
+        let mut axes = self.axes.iter();
+        let first = axes.next().map(|i| i.len()).unwrap_or_default();
+        axes.fold(first, |acc, i| acc * i.len())
+    }
+
+    pub fn shift(&self, delta: [T; D]) -> IntervalBox<T, D> {
+        let mut axes = self.axes;
+        for (interval, d) in axes.iter_mut().zip(delta) {
+            *interval = interval.shift(d);
+        }
+        IntervalBox { axes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    fn ranges(set: &IntervalSet<i64>) -> Vec<(i64, i64)> {
+        set.iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    #[test]
+    fn inserting_merges() {
+        // Touching.
+        assert_eq!(ranges(&set(&[(0, 3), (3, 5)])), [(0, 5)]);
+        // Overlapping, and bridging several.
+        assert_eq!(ranges(&set(&[(0, 2), (4, 6), (8, 10), (1, 9)])), [(0, 10)]);
+        // Neither.
+        assert_eq!(
+            ranges(&set(&[(5, 7), (0, 2), (3, 4)])),
+            [(0, 2), (3, 4), (5, 7)]
+        );
+        // Empty intervals are left out.
+        assert_eq!(ranges(&set(&[(3, 3), (5, 4)])), []);
+        assert_eq!(set(&[(0, 3), (5, 7)]).len(), 5);
+    }
+
+    #[test]
+    fn interval_differences() {
+        let interval = Interval::new(0, 10);
+        let (below, above) = interval.difference(&Interval::new(3, 5));
+        assert_eq!((below, above), (Interval::new(0, 3), Interval::new(5, 10)));
+
+        let (below, above) = interval.difference(&Interval::new(-5, 20));
+        assert!(below.is_empty() && above.is_empty());
+        let (below, above) = interval.difference(&Interval::new(20, 30));
+        assert_eq!(below, interval);
+        assert!(above.is_empty());
+        let (below, above) = interval.difference(&Interval::new(4, 4));
+        assert_eq!(below, interval);
+        assert!(above.is_empty());
+    }
+
+    #[test]
+    fn set_differences() {
+        let a = set(&[(0, 10), (20, 30)]);
+        assert_eq!(ranges(&a.difference(&set(&[(5, 25)]))), [(0, 5), (25, 30)]);
+        assert_eq!(ranges(&a.difference(&set(&[(0, 10)]))), [(20, 30)]);
+        assert_eq!(ranges(&a.difference(&set(&[(-10, 40)]))), []);
+        assert_eq!(
+            ranges(&a.difference(&set(&[(12, 18)]))),
+            [(0, 10), (20, 30)]
+        );
+    }
+
+    #[test]
+    fn interval_splits() {
+        let interval = Interval::new(0, 10);
+        assert_eq!(
+            interval.split_at(4),
+            (Interval::new(0, 4), Interval::new(4, 10))
+        );
+        // At and outside of the bounds, one side is empty.
+        assert_eq!(
+            interval.split_at(0),
+            (Interval::new(0, 0), Interval::new(0, 10))
+        );
+        assert_eq!(
+            interval.split_at(-5),
+            (Interval::new(0, 0), Interval::new(0, 10))
+        );
+        assert_eq!(
+            interval.split_at(10),
+            (Interval::new(0, 10), Interval::new(10, 10))
+        );
+        assert_eq!(
+            interval.split_at(15),
+            (Interval::new(0, 10), Interval::new(10, 10))
+        );
+    }
+
+    #[test]
+    fn set_splits() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let (below, above) = a.split_at(25);
+        assert_eq!(
+            (ranges(&below), ranges(&above)),
+            (vec![(0, 10), (20, 25)], vec![(25, 30)])
+        );
+        let (below, above) = a.split_at(15);
+        assert_eq!(
+            (ranges(&below), ranges(&above)),
+            (vec![(0, 10)], vec![(20, 30)])
+        );
+        let (below, above) = a.split_at(-1);
+        assert_eq!(
+            (ranges(&below), ranges(&above)),
+            (vec![], vec![(0, 10), (20, 30)])
+        );
+        let (below, above) = a.split_at(30);
+        assert_eq!(
+            (ranges(&below), ranges(&above)),
+            (vec![(0, 10), (20, 30)], vec![])
+        );
+    }
+
+    #[test]
+    fn boxes() {
+        let cube = IntervalBox::new([(0..4).into(), (0..3).into(), (0..2).into()]);
+        assert_eq!(cube.volume(), 24);
+
+        let (lower, upper) = cube.split_at(1, 1);
+        assert_eq!(lower.axes[1], Interval::new(0, 1));
+        assert_eq!(upper.axes[1], Interval::new(1, 3));
+        assert_eq!(lower.axes[0], cube.axes[0]);
+        assert_eq!(lower.volume() + upper.volume(), cube.volume());
+
+        let (lower, upper) = cube.split_at(2, 5);
+        assert_eq!(lower, cube);
+        assert!(upper.is_empty());
+        assert_eq!(upper.volume(), 0);
+    }
+}
@@ -0,0 +1,18 @@
+// Code shared between the days.
+
+pub mod anonymize;
+pub mod assumptions;
+pub mod automaton;
+pub mod bits;
+pub mod checked;
+pub mod cli;
+pub mod graph;
+pub mod interval;
+#[cfg(feature = "model")]
+pub mod model;
+pub mod normalize;
+pub mod parallel;
+pub mod parse;
+pub mod solvers;
+pub mod stream;
+pub mod vector;
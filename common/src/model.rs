@@ -0,0 +1,163 @@
+//! Parsing dominates the time of some days on big generated inputs. Their parsed input, the model,
+//! can be saved to a compact binary file (MessagePack, after a header naming the puzzle) with
+//! `--save-model PATH`, and given to the day instead of the text. That way benchmarks measure
+//! solving alone, and stress corpora can be kept in a structured form.
+//!
+//! The header also has the version of the layout of the model, which a day raises whenever it
+//! changes its model. So a kept model of an older layout is reported as such rather than failing
+//! to decode.
+
+use crate::stream::{Source, StreamError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// How a model file starts. Text inputs never do, they're printable.
+const MAGIC: &[u8] = b"\0aoc-model\n";
+
+#[derive(Debug)]
+pub enum ModelError {
+    Stream(StreamError),
+    Io(io::Error),
+    Encode(rmp_serde::encode::Error),
+    Decode(rmp_serde::decode::Error),
+    OtherPuzzle { expected: String, found: String },
+    OtherVersion { expected: u32, found: u32 },
+}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelError::Stream(e) => write!(f, "{}", e),
+            ModelError::Io(e) => write!(f, "{}", e),
+            ModelError::Encode(e) => write!(f, "can't save the model: {}", e),
+            ModelError::Decode(e) => write!(f, "invalid model: {}", e),
+            ModelError::OtherPuzzle { expected, found } => {
+                write!(f, "the model is one of {}, not {}", found, expected)
+            }
+            ModelError::OtherVersion { expected, found } => write!(
+                f,
+                "model of an {} format (version {}, this build reads version {}), save it again",
+                if found < expected { "older" } else { "newer" },
+                found,
+                expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ModelError {}
+
+/// What a source held: a saved model, or text that still has to be parsed.
+pub enum Loaded<T> {
+    Model(T),
+    Text(String),
+}
+
+pub fn is_model(bytes: &[u8]) -> bool {
+    bytes.starts_with(MAGIC)
+}
+
+/// Reads the source, which may be a model saved by `save` for the puzzle (like "2023/day5") in the
+/// same version. Text is normalized (or checked, with `--strict`) as by `Source::read`.
+pub fn read<T: DeserializeOwned>(
+    source: &Source,
+    puzzle: &str,
+    version: u32,
+) -> Result<Loaded<T>, ModelError> {
+    let bytes = source.read_bytes().map_err(ModelError::Stream)?;
+    if !is_model(&bytes) {
+        let text = source.text(bytes).map_err(ModelError::Stream)?;
+        return Ok(Loaded::Text(text));
+    }
+
+    // The header comes first, so a model of another puzzle or version isn't even decoded.
+    let mut deserializer = rmp_serde::Deserializer::new(&bytes[MAGIC.len()..]);
+    let (found, found_version) =
+        <(String, u32)>::deserialize(&mut deserializer).map_err(ModelError::Decode)?;
+    if found != puzzle {
+        return Err(ModelError::OtherPuzzle {
+            expected: puzzle.to_string(),
+            found,
+        });
+    }
+    if found_version != version {
+        return Err(ModelError::OtherVersion {
+            expected: version,
+            found: found_version,
+        });
+    }
+    let model = T::deserialize(&mut deserializer).map_err(ModelError::Decode)?;
+    Ok(Loaded::Model(model))
+}
+
+/// Writes the model of the puzzle to the file, so it can be read back by `read`.
+pub fn save<T: Serialize>(
+    path: &Path,
+    puzzle: &str,
+    version: u32,
+    model: &T,
+) -> Result<(), ModelError> {
+    let file = File::create(path).map_err(ModelError::Io)?;
+    let mut writer = BufWriter::new(file);
+    writer.write_all(MAGIC).map_err(ModelError::Io)?;
+    rmp_serde::encode::write(&mut writer, &(puzzle, version)).map_err(ModelError::Encode)?;
+    rmp_serde::encode::write(&mut writer, model).map_err(ModelError::Encode)?;
+    writer.flush().map_err(ModelError::Io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::Origin;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc-model-{}-{}", std::process::id(), name))
+    }
+
+    fn read_file(path: &Path, puzzle: &str, version: u32) -> Result<Vec<u32>, ModelError> {
+        let source = Source {
+            origin: Origin::File(path.to_path_buf()),
+            strict: false,
+        };
+        match read(&source, puzzle, version)? {
+            Loaded::Model(model) => Ok(model),
+            Loaded::Text(text) => panic!("read as text: {:?}", text),
+        }
+    }
+
+    #[test]
+    fn versions() {
+        let path = file("versions");
+        save(&path, "2023/day2", 2, &vec![1u32, 2]).unwrap();
+        assert_eq!(read_file(&path, "2023/day2", 2).unwrap(), [1, 2]);
+        let error = read_file(&path, "2023/day2", 3).unwrap_err();
+        assert!(matches!(
+            error,
+            ModelError::OtherVersion {
+                expected: 3,
+                found: 2
+            }
+        ));
+        let error = read_file(&path, "2023/day5", 2).unwrap_err();
+        assert!(matches!(error, ModelError::OtherPuzzle { .. }));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn models_of_an_older_format() {
+        let path = file("older");
+        save(&path, "2023/day2", 1, &vec![7u32]).unwrap();
+        let error = read_file(&path, "2023/day2", 2).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "model of an older format (version 1, this build reads version 2), save it again"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
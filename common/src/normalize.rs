@@ -0,0 +1,291 @@
+//! Inputs that went through an editor or a Windows machine can differ from the downloaded ones in
+//! ways that are hard to see, but trip up the parsers: a byte order mark, CRLF line endings,
+//! blanks at the end of lines, blank lines at the end, or no final newline. `Source` normalizes
+//! them away before a day gets to see the input, or reports them with `--strict`.
+
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Issue {
+    ByteOrderMark,
+    CarriageReturns { first_line: usize, lines: usize },
+    TrailingWhitespace { first_line: usize, lines: usize },
+    TrailingBlankLines(usize),
+    MissingFinalNewline,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::ByteOrderMark => write!(f, "starts with a byte order mark"),
+            Issue::CarriageReturns { first_line, lines } => write!(
+                f,
+                "carriage returns (CRLF) on {} line(s), the first is line {}",
+                lines, first_line
+            ),
+            Issue::TrailingWhitespace { first_line, lines } => write!(
+                f,
+                "blanks at the end of {} line(s), the first is line {}",
+                lines, first_line
+            ),
+            Issue::TrailingBlankLines(lines) => write!(f, "ends with {} blank line(s)", lines),
+            Issue::MissingFinalNewline => write!(f, "the last line has no newline"),
+        }
+    }
+}
+
+const BYTE_ORDER_MARK: &[u8] = "\u{feff}".as_bytes();
+
+/// Finds the issues of an input that is fed to it in pieces, like a stream.
+pub struct Checker {
+    position: usize,
+    line: usize,
+    previous: Option<u8>,
+    // Whether the current line has nothing but blanks (so far), or a carriage return or trailing
+    // blanks.
+    line_blank: bool,
+    line_carriage_return: bool,
+    line_trailing_whitespace: bool,
+    blank_lines: usize,
+    byte_order_mark: bool,
+    carriage_returns: Option<(usize, usize)>,
+    trailing_whitespace: Option<(usize, usize)>,
+}
+
+// Counts the line as having the issue, once.
+fn count_line(issue: &mut Option<(usize, usize)>, line: usize) {
+    match issue {
+        None => *issue = Some((line, 1)),
+        Some((_, lines)) => *lines += 1,
+    }
+}
+
+impl Checker {
+    pub fn new() -> Checker {
+        Checker {
+            position: 0,
+            line: 1,
+            previous: None,
+            line_blank: true,
+            line_carriage_return: false,
+            line_trailing_whitespace: false,
+            blank_lines: 0,
+            byte_order_mark: true,
+            carriage_returns: None,
+            trailing_whitespace: None,
+        }
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            if self.position < BYTE_ORDER_MARK.len() {
+                self.byte_order_mark &= byte == BYTE_ORDER_MARK[self.position];
+            }
+            let in_byte_order_mark = self.byte_order_mark && self.position < BYTE_ORDER_MARK.len();
+            self.position += 1;
+
+            match byte {
+                b'\r' | b'\n' => {
+                    if matches!(self.previous, Some(b' ' | b'\t')) && !self.line_trailing_whitespace
+                    {
+                        count_line(&mut self.trailing_whitespace, self.line);
+                        self.line_trailing_whitespace = true;
+                    }
+                    if byte == b'\r' && !self.line_carriage_return {
+                        count_line(&mut self.carriage_returns, self.line);
+                        self.line_carriage_return = true;
+                    }
+                    if byte == b'\n' {
+                        if self.line_blank {
+                            self.blank_lines += 1;
+                        } else {
+                            self.blank_lines = 0;
+                        }
+                        self.line += 1;
+                        self.line_blank = true;
+                        self.line_carriage_return = false;
+                        self.line_trailing_whitespace = false;
+                    }
+                }
+                b' ' | b'\t' => {}
+                _ if in_byte_order_mark => {}
+                _ => self.line_blank = false,
+            }
+            self.previous = Some(byte);
+        }
+    }
+
+    /// The issues found so far, without those that depend on how the input ends.
+    pub fn found(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        if self.byte_order_mark && self.position >= BYTE_ORDER_MARK.len() {
+            issues.push(Issue::ByteOrderMark);
+        }
+        if let Some((first_line, lines)) = self.carriage_returns {
+            issues.push(Issue::CarriageReturns { first_line, lines });
+        }
+        if let Some((first_line, lines)) = self.trailing_whitespace {
+            issues.push(Issue::TrailingWhitespace { first_line, lines });
+        }
+        issues
+    }
+
+    /// The issues of all that was fed, as the end of the input has been reached.
+    pub fn finish(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        if self.byte_order_mark && self.position >= BYTE_ORDER_MARK.len() {
+            issues.push(Issue::ByteOrderMark);
+        }
+        if let Some((first_line, lines)) = self.carriage_returns {
+            issues.push(Issue::CarriageReturns { first_line, lines });
+        }
+
+        let mut trailing_whitespace = self.trailing_whitespace;
+        let mut blank_lines = self.blank_lines;
+        let mut missing_newline = false;
+        // The last line, if it isn't terminated.
+        if self.previous.is_some_and(|b| b != b'\n') {
+            if matches!(self.previous, Some(b' ' | b'\t')) && !self.line_trailing_whitespace {
+                count_line(&mut trailing_whitespace, self.line);
+            }
+            if self.line_blank {
+                blank_lines += 1;
+            } else {
+                blank_lines = 0;
+                missing_newline = true;
+            }
+        }
+
+        if let Some((first_line, lines)) = trailing_whitespace {
+            issues.push(Issue::TrailingWhitespace { first_line, lines });
+        }
+        if blank_lines > 0 {
+            issues.push(Issue::TrailingBlankLines(blank_lines));
+        }
+        if missing_newline {
+            issues.push(Issue::MissingFinalNewline);
+        }
+        issues
+    }
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        Checker::new()
+    }
+}
+
+/// What `normalize` would change about the input.
+pub fn issues(input: &str) -> Vec<Issue> {
+    let mut checker = Checker::new();
+    checker.feed(input.as_bytes());
+    checker.finish()
+}
+
+/// The input without byte order mark, with '\n' line endings (CRLF and single carriage returns
+/// become one), without blanks at the end of lines or blank lines at the end, but with a final
+/// newline.
+pub fn normalize(input: &str) -> String {
+    let input = input.trim_start_matches('\u{feff}');
+    let input = input.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut normalized = String::with_capacity(input.len() + 1);
+    for line in input.lines() {
+        normalized += line.trim_end_matches([' ', '\t']);
+        normalized.push('\n');
+    }
+    let end = normalized.trim_end_matches('\n').len();
+    normalized.truncate(end);
+    if !normalized.is_empty() {
+        normalized.push('\n');
+    }
+    normalized
+}
+
+/// Every text of up to `len` of the characters that matter to normalizing, with and without a byte
+/// order mark. For tests that have to hold for all of them.
+#[cfg(test)]
+pub(crate) fn texts(len: usize) -> Vec<String> {
+    let mut texts = vec![String::new()];
+    let mut last = vec![String::new()];
+    for _ in 0..len {
+        last = last
+            .iter()
+            .flat_map(|t| ["a", " ", "\t", "\r", "\n"].map(|c| format!("{}{}", t, c)))
+            .collect();
+        texts.extend(last.iter().cloned());
+    }
+    let marked: Vec<String> = texts.iter().map(|t| format!("\u{feff}{}", t)).collect();
+    texts.extend(marked);
+    texts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issues_are_what_normalizing_changes() {
+        let cases = [
+            ("", vec![]),
+            ("a\nb\n", vec![]),
+            ("\na\n", vec![]),
+            ("\u{feff}a\n", vec![Issue::ByteOrderMark]),
+            (
+                "a\rb\n",
+                vec![Issue::CarriageReturns {
+                    first_line: 1,
+                    lines: 1,
+                }],
+            ),
+            (
+                "a\nb\r\nc\r\n",
+                vec![Issue::CarriageReturns {
+                    first_line: 2,
+                    lines: 2,
+                }],
+            ),
+            (
+                "a\t\nb\n \nc\n",
+                vec![Issue::TrailingWhitespace {
+                    first_line: 1,
+                    lines: 2,
+                }],
+            ),
+            ("a\n\n\n", vec![Issue::TrailingBlankLines(2)]),
+            (
+                "a\n \t",
+                vec![
+                    Issue::TrailingWhitespace {
+                        first_line: 2,
+                        lines: 1,
+                    },
+                    Issue::TrailingBlankLines(1),
+                ],
+            ),
+            ("a\nb", vec![Issue::MissingFinalNewline]),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(issues(input), expected, "{:?}", input);
+            assert_eq!(
+                expected.is_empty(),
+                normalize(input) == input,
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn issues_exactly_when_normalizing_changes_something() {
+        for text in texts(6) {
+            assert_eq!(
+                issues(&text).is_empty(),
+                normalize(&text) == text,
+                "{:?} has {:?}",
+                text,
+                issues(&text)
+            );
+        }
+    }
+}
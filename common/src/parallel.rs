@@ -0,0 +1,27 @@
+//! Support for the days' `parallel` cargo feature, which spreads their hot loops over a rayon
+//! thread pool. The days enable the feature of this crate along with their own.
+
+use crate::cli::{Args, UsageError};
+
+/// Sets up the thread pool from `--threads N`, using one thread per core by default. Without the
+/// `parallel` feature everything runs on the calling thread, and the option is refused.
+pub fn init_threads(args: &mut Args) -> Result<(), UsageError> {
+    let threads: Option<usize> = args.value("--threads")?;
+
+    #[cfg(feature = "parallel")]
+    if let Some(threads) = threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| UsageError(e.to_string()))?;
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    if threads.is_some() {
+        return Err(UsageError(
+            "--threads needs a build with the parallel feature".to_string(),
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,606 @@
+//! Small parser combinators. A parser is a function taking the remaining input and returning the
+//! parsed value together with the input that is left. When parsing fails, the error knows the line
+//! and column it happened at, and what was expected there.
+
+use std::{fmt::Display, str::FromStr};
+
+/// Position in the input, both starting at 1. Columns are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub location: Location,
+    pub expected: String,
+    pub found: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.location, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    /// Moves the error to where the parsed text started in a larger input, for text that was cut
+    /// out of it (like a single line read from a stream).
+    pub fn relative_to(mut self, start: Location) -> ParseError {
+        if self.location.line == 1 {
+            self.location.column += start.column - 1;
+        }
+        self.location.line += start.line - 1;
+        self
+    }
+}
+
+/// The part of the input that still has to be parsed. Remembers where it is in the whole input so
+/// errors can be located.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Input<'a> {
+    source: &'a str,
+    position: usize,
+    // Kept up to date while advancing, so locating an error doesn't need to scan the whole input.
+    line: usize,
+    line_start: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Input<'a> {
+        Input {
+            source,
+            position: 0,
+            line: 1,
+            line_start: 0,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// Skips the given number of bytes, which must end on a character boundary.
+    pub fn advance(&self, bytes: usize) -> Input<'a> {
+        let position = self.position + bytes;
+        let skipped = &self.source[self.position..position];
+        let mut line = self.line;
+        let mut line_start = self.line_start;
+        if let Some(last) = skipped.rfind('\n') {
+            line += skipped.matches('\n').count();
+            line_start = self.position + last + 1;
+        }
+
+        Input {
+            source: self.source,
+            position,
+            line,
+            line_start,
+        }
+    }
+
+    pub fn location(&self) -> Location {
+        Location {
+            line: self.line,
+            column: self.source[self.line_start..self.position].chars().count() + 1,
+        }
+    }
+
+    /// An error at the current position.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let line = self.rest().lines().next().unwrap_or("");
+        let found = if self.is_empty() {
+            "end of input".to_string()
+        } else if line.is_empty() {
+            "end of line".to_string()
+        } else if line.chars().count() > 20 {
+            format!("\"{}…\"", line.chars().take(20).collect::<String>())
+        } else {
+            format!("\"{}\"", line)
+        };
+
+        ParseError {
+            location: self.location(),
+            expected: expected.into(),
+            found,
+        }
+    }
+}
+
+pub type ParseResult<'a, T> = Result<(T, Input<'a>), ParseError>;
+
+/// Runs the parser on the whole input. Leading blank lines are skipped, and only whitespace may be
+/// left over.
+pub fn parse<'a, T, P>(parser: P, input: &'a str) -> Result<T, ParseError>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    let start = Input::new(input);
+    let start = start.advance(input.len() - input.trim_start_matches(['\r', '\n']).len());
+    let (value, rest) = parser(start)?;
+    let trimmed = rest.rest().trim_start();
+    let rest = rest.advance(rest.rest().len() - trimmed.len());
+    if !rest.is_empty() {
+        return Err(rest.error("end of input"));
+    }
+
+    Ok(value)
+}
+
+/// Matches the given text exactly.
+pub fn tag<'a, 't>(tag: &'t str) -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> + 't {
+    move |input: Input<'a>| {
+        if input.rest().starts_with(tag) {
+            Ok((&input.rest()[..tag.len()], input.advance(tag.len())))
+        } else {
+            Err(input.error(format!("\"{}\"", tag)))
+        }
+    }
+}
+
+/// One or more characters matching the predicate.
+pub fn take_while1<'a, F>(
+    expected: &'static str,
+    predicate: F,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let len = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(input.error(expected));
+        }
+
+        Ok((&rest[..len], input.advance(len)))
+    }
+}
+
+/// One or more alphanumeric characters.
+pub fn word<'a>() -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    take_while1("word", char::is_alphanumeric)
+}
+
+/// Decimal integer with an optional leading minus sign. Fails if the value doesn't fit into `T`.
+pub fn integer<'a, T: FromStr>() -> impl Fn(Input<'a>) -> ParseResult<'a, T> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let sign = if rest.starts_with('-') { 1 } else { 0 };
+        let digits = rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - sign);
+        if digits == 0 {
+            return Err(input.error("integer"));
+        }
+
+        let len = sign + digits;
+        match rest[..len].parse() {
+            Ok(value) => Ok((value, input.advance(len))),
+            Err(_) => Err(input.error(format!("integer fitting {}", std::any::type_name::<T>()))),
+        }
+    }
+}
+
+/// A single character, converted by `f`. Fails if `f` returns `None`.
+pub fn symbol<'a, T, F>(expected: &'static str, f: F) -> impl Fn(Input<'a>) -> ParseResult<'a, T>
+where
+    F: Fn(char) -> Option<T>,
+{
+    move |input: Input<'a>| {
+        let c = input
+            .rest()
+            .chars()
+            .next()
+            .ok_or_else(|| input.error(expected))?;
+        let value = f(c).ok_or_else(|| input.error(expected))?;
+        Ok((value, input.advance(c.len_utf8())))
+    }
+}
+
+/// The value belonging to the first of the given texts that matches.
+pub fn keyword<'a, 't, T: Clone>(
+    options: &'t [(&'t str, T)],
+) -> impl Fn(Input<'a>) -> ParseResult<'a, T> + 't {
+    move |input: Input<'a>| {
+        for (text, value) in options {
+            if input.rest().starts_with(text) {
+                return Ok((value.clone(), input.advance(text.len())));
+            }
+        }
+
+        let names: Vec<String> = options.iter().map(|(t, _)| format!("\"{}\"", t)).collect();
+        Err(input.error(format!("one of {}", names.join(", "))))
+    }
+}
+
+/// Zero or more spaces or tabs.
+pub fn space0<'a>() -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let len = rest.find(|c| c != ' ' && c != '\t').unwrap_or(rest.len());
+        Ok((&rest[..len], input.advance(len)))
+    }
+}
+
+/// One or more spaces or tabs.
+pub fn space1<'a>() -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    take_while1("space", |c| c == ' ' || c == '\t')
+}
+
+/// "\n" or "\r\n".
+pub fn line_end<'a>() -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        for end in ["\n", "\r\n"] {
+            if input.rest().starts_with(end) {
+                return Ok((end, input.advance(end.len())));
+            }
+        }
+        Err(input.error("end of line"))
+    }
+}
+
+/// Everything up to the end of the line, which may be empty.
+pub fn rest_of_line<'a>() -> impl Fn(Input<'a>) -> ParseResult<'a, &'a str> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let len = rest.find(['\r', '\n']).unwrap_or(rest.len());
+        Ok((&rest[..len], input.advance(len)))
+    }
+}
+
+pub fn map<'a, T, U, P, F>(parser: P, f: F) -> impl Fn(Input<'a>) -> ParseResult<'a, U>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+    F: Fn(T) -> U,
+{
+    move |input: Input<'a>| {
+        let (value, rest) = parser(input)?;
+        Ok((f(value), rest))
+    }
+}
+
+/// Like `map`, but the conversion may fail. The error is reported at the start of the parsed
+/// value.
+pub fn try_map<'a, T, U, P, F>(
+    parser: P,
+    expected: &'static str,
+    f: F,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, U>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+    F: Fn(T) -> Option<U>,
+{
+    move |input: Input<'a>| {
+        let (value, rest) = parser(input)?;
+        let value = f(value).ok_or_else(|| input.error(expected))?;
+        Ok((value, rest))
+    }
+}
+
+pub fn pair<'a, A, B, PA, PB>(
+    first: PA,
+    second: PB,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, (A, B)>
+where
+    PA: Fn(Input<'a>) -> ParseResult<'a, A>,
+    PB: Fn(Input<'a>) -> ParseResult<'a, B>,
+{
+    move |input: Input<'a>| {
+        let (a, rest) = first(input)?;
+        let (b, rest) = second(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// Runs both parsers, keeping the value of the second.
+pub fn preceded<'a, A, B, PA, PB>(first: PA, second: PB) -> impl Fn(Input<'a>) -> ParseResult<'a, B>
+where
+    PA: Fn(Input<'a>) -> ParseResult<'a, A>,
+    PB: Fn(Input<'a>) -> ParseResult<'a, B>,
+{
+    map(pair(first, second), |(_, b)| b)
+}
+
+/// Runs both parsers, keeping the value of the first.
+pub fn terminated<'a, A, B, PA, PB>(
+    first: PA,
+    second: PB,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, A>
+where
+    PA: Fn(Input<'a>) -> ParseResult<'a, A>,
+    PB: Fn(Input<'a>) -> ParseResult<'a, B>,
+{
+    map(pair(first, second), |(a, _)| a)
+}
+
+/// Runs all three parsers, keeping the value of the one in the middle.
+pub fn delimited<'a, A, B, C, PA, PB, PC>(
+    open: PA,
+    parser: PB,
+    close: PC,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, B>
+where
+    PA: Fn(Input<'a>) -> ParseResult<'a, A>,
+    PB: Fn(Input<'a>) -> ParseResult<'a, B>,
+    PC: Fn(Input<'a>) -> ParseResult<'a, C>,
+{
+    preceded(open, terminated(parser, close))
+}
+
+/// A `key`, followed by `separator` and `value`, like "x=42".
+pub fn key_value<'a, K, S, V, PK, PS, PV>(
+    key: PK,
+    separator: PS,
+    value: PV,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, (K, V)>
+where
+    PK: Fn(Input<'a>) -> ParseResult<'a, K>,
+    PS: Fn(Input<'a>) -> ParseResult<'a, S>,
+    PV: Fn(Input<'a>) -> ParseResult<'a, V>,
+{
+    pair(terminated(key, separator), value)
+}
+
+/// Tries the first parser, and the second one if the first fails.
+pub fn either<'a, T, PA, PB>(first: PA, second: PB) -> impl Fn(Input<'a>) -> ParseResult<'a, T>
+where
+    PA: Fn(Input<'a>) -> ParseResult<'a, T>,
+    PB: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    move |input: Input<'a>| first(input).or_else(|_| second(input))
+}
+
+/// `None` if the parser fails, without consuming anything.
+pub fn opt<'a, T, P>(parser: P) -> impl Fn(Input<'a>) -> ParseResult<'a, Option<T>>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    move |input: Input<'a>| match parser(input) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(_) => Ok((None, input)),
+    }
+}
+
+/// One or more values directly following each other.
+pub fn many1<'a, T, P>(parser: P) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    move |input: Input<'a>| {
+        let (first, mut rest) = parser(input)?;
+        let mut values = vec![first];
+        while let Ok((value, next)) = parser(rest) {
+            if next == rest {
+                // Parser doesn't consume anything, would loop forever.
+                break;
+            }
+            values.push(value);
+            rest = next;
+        }
+        Ok((values, rest))
+    }
+}
+
+/// One or more values with a separator in between. A trailing separator is not consumed. If a value
+/// after a separator fails after consuming some input, that's reported as error instead of
+/// stopping the list there.
+pub fn separated<'a, T, S, P, PS>(
+    parser: P,
+    separator: PS,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+    PS: Fn(Input<'a>) -> ParseResult<'a, S>,
+{
+    move |input: Input<'a>| {
+        let (first, mut rest) = parser(input)?;
+        let mut values = vec![first];
+        while let Ok((_, next)) = separator(rest) {
+            match parser(next) {
+                Ok((value, after)) => {
+                    values.push(value);
+                    rest = after;
+                }
+                Err(error) if error.location != next.location() => return Err(error),
+                Err(_) => break,
+            }
+        }
+        Ok((values, rest))
+    }
+}
+
+// Whether the input is at a blank line or only whitespace is left.
+fn is_section_end(input: Input) -> bool {
+    input.rest().trim().is_empty() || line_end()(input).is_ok()
+}
+
+// The parser has to stop right at the end of the line.
+fn whole_line<'a, T, P>(parser: P) -> impl Fn(Input<'a>) -> ParseResult<'a, T>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    move |input: Input<'a>| {
+        let (value, rest) = parser(input)?;
+        if !rest.is_empty() && line_end()(rest).is_err() {
+            return Err(rest.error("end of line"));
+        }
+        Ok((value, rest))
+    }
+}
+
+/// One value per line, up to a blank line or the end of the input. Every line in between has to
+/// be a valid value.
+pub fn lines<'a, T, P>(parser: P) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    let parser = whole_line(parser);
+    move |input: Input<'a>| {
+        let (first, mut rest) = parser(input)?;
+        let mut values = vec![first];
+        while let Ok((_, next)) = line_end()(rest) {
+            if is_section_end(next) {
+                break;
+            }
+
+            let (value, after) = parser(next)?;
+            values.push(value);
+            rest = after;
+        }
+        Ok((values, rest))
+    }
+}
+
+/// Sections separated by a blank line.
+pub fn blocks<'a, T, P>(parser: P) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<T>>
+where
+    P: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    let blank_line = pair(line_end(), line_end());
+    move |input: Input<'a>| {
+        let (first, mut rest) = parser(input)?;
+        let mut values = vec![first];
+        while let Ok((_, next)) = blank_line(rest) {
+            if next.rest().trim().is_empty() {
+                break;
+            }
+
+            let (value, after) = parser(next)?;
+            values.push(value);
+            rest = after;
+        }
+        Ok((values, rest))
+    }
+}
+
+/// Rectangular block of characters, one row per line, each character converted by `cell`. Fails if
+/// the rows are not all the same length.
+pub fn grid<'a, T, F>(
+    expected: &'static str,
+    cell: F,
+) -> impl Fn(Input<'a>) -> ParseResult<'a, Vec<Vec<T>>>
+where
+    F: Fn(char) -> Option<T>,
+{
+    let cells = many1(symbol(expected, cell));
+    // Reports unexpected characters as invalid cells, rather than as missing end of line.
+    let row = move |input: Input<'a>| {
+        let (cells, rest) = cells(input)?;
+        if !rest.is_empty() && line_end()(rest).is_err() {
+            return Err(rest.error(expected));
+        }
+        Ok((cells, rest))
+    };
+    move |input: Input<'a>| {
+        let (first, mut rest) = row(input)?;
+        let width = first.len();
+        let mut rows = vec![first];
+
+        while let Ok((_, next)) = line_end()(rest) {
+            if is_section_end(next) {
+                break;
+            }
+
+            let (cells, after) = row(next)?;
+            if cells.len() != width {
+                return Err(next.error(format!("row of {} cells", width)));
+            }
+            rows.push(cells);
+            rest = after;
+        }
+
+        Ok((rows, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn location(error: ParseError) -> (usize, usize) {
+        (error.location.line, error.location.column)
+    }
+
+    fn cell(c: char) -> Option<bool> {
+        match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn errors_in_lines() {
+        let numbers = || lines(integer::<u32>());
+        assert_eq!(parse(numbers(), "1\n2\n").unwrap(), [1, 2]);
+
+        let error = parse(numbers(), "1\n2\nx3\n").unwrap_err();
+        assert_eq!(location(error), (3, 1));
+        let error = parse(numbers(), "1\n22a\n").unwrap_err();
+        assert_eq!(location(error.clone()), (2, 3));
+        assert_eq!(error.expected, "end of line");
+        assert_eq!(error.found, "\"a\"");
+
+        // Skipped blank lines still count.
+        let error = parse(numbers(), "\n\n1\nx\n").unwrap_err();
+        assert_eq!(location(error), (4, 1));
+        // Columns count characters, not bytes.
+        let error = parse(lines(word()), "ab\näü-\n").unwrap_err();
+        assert_eq!(location(error), (2, 3));
+    }
+
+    #[test]
+    fn errors_in_blocks() {
+        let sections = || blocks(lines(integer::<i32>()));
+        assert_eq!(
+            parse(sections(), "1\n2\n\n3\n").unwrap(),
+            [vec![1, 2], vec![3]]
+        );
+
+        let error = parse(sections(), "1\n2\n\n3\n-\n").unwrap_err();
+        assert_eq!(location(error), (5, 1));
+        let error = parse(sections(), "1\n\n2\n\n3 4\n").unwrap_err();
+        assert_eq!(location(error), (5, 2));
+    }
+
+    #[test]
+    fn errors_in_grids() {
+        let tiles = || grid("'#' or '.'", cell);
+        assert_eq!(
+            parse(tiles(), "#.\n.#\n").unwrap(),
+            [[true, false], [false, true]]
+        );
+
+        let error = parse(tiles(), "#.#\n#x#\n").unwrap_err();
+        assert_eq!(location(error.clone()), (2, 2));
+        assert_eq!(error.expected, "'#' or '.'");
+        let error = parse(tiles(), "#.#\n###\n##\n").unwrap_err();
+        assert_eq!(location(error.clone()), (3, 1));
+        assert_eq!(error.expected, "row of 3 cells");
+    }
+
+    #[test]
+    fn errors_relative_to_a_line() {
+        let error = parse(integer::<u8>(), "300").unwrap_err();
+        let error = error.relative_to(Location { line: 7, column: 5 });
+        assert_eq!(location(error), (7, 5));
+    }
+}
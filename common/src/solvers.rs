@@ -0,0 +1,91 @@
+//! When a faster algorithm replaces a slow one, the original stays around as a reference. A day
+//! lists all implementations of its parts as solvers, the one it normally uses first.
+//! `--cross-check` runs all of them and compares their answers.
+
+use std::fmt::Display;
+use std::time::Instant;
+
+pub struct Solver<I: ?Sized, A, E> {
+    pub part: u32,
+    pub name: &'static str,
+    pub solve: fn(&I) -> Result<A, E>,
+}
+
+/// Runs every solver on the input and prints its answer. Returns whether all solvers of a part
+/// agree, for every part.
+pub fn cross_check<I, A, E>(input: &I, solvers: &[Solver<I, A, E>]) -> Result<bool, E>
+where
+    I: ?Sized,
+    A: PartialEq + Display,
+{
+    let mut all_agree = true;
+    let mut parts: Vec<u32> = Vec::new();
+    for solver in solvers {
+        if !parts.contains(&solver.part) {
+            parts.push(solver.part);
+        }
+    }
+
+    for part in parts {
+        let mut answers = Vec::new();
+        for solver in solvers.iter().filter(|s| s.part == part) {
+            let start = Instant::now();
+            let answer = (solver.solve)(input)?;
+            println!(
+                "Part {} ({}): {} in {:.2?}",
+                part,
+                solver.name,
+                answer,
+                start.elapsed()
+            );
+            answers.push(answer);
+        }
+
+        if answers.len() == 1 {
+            println!("Part {}: nothing to compare\n", part);
+        } else if answers.iter().all(|a| *a == answers[0]) {
+            println!("Part {}: all {} solvers agree\n", part, answers.len());
+        } else {
+            println!("Part {}: SOLVERS DISAGREE\n", part);
+            all_agree = false;
+        }
+    }
+
+    Ok(all_agree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solver(part: u32, solve: fn(&str) -> Result<usize, String>) -> Solver<str, usize, String> {
+        Solver {
+            part,
+            name: "test",
+            solve,
+        }
+    }
+
+    #[test]
+    fn agreement() {
+        let agreeing = [
+            solver(1, |input| Ok(input.len())),
+            solver(1, |input| Ok(input.chars().count())),
+            solver(2, |_| Ok(7)),
+        ];
+        assert_eq!(cross_check("abc", &agreeing), Ok(true));
+
+        let disagreeing = [
+            solver(1, |input| Ok(input.len())),
+            solver(2, |_| Ok(7)),
+            solver(2, |_| Ok(8)),
+        ];
+        assert_eq!(cross_check("abc", &disagreeing), Ok(false));
+    }
+
+    #[test]
+    fn errors_are_passed_on() {
+        let failing = [solver(1, |_| Ok(1)), solver(1, |_| Err("no".to_string()))];
+        assert_eq!(cross_check("abc", &failing), Err("no".to_string()));
+    }
+}
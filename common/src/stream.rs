@@ -0,0 +1,390 @@
+//! Reading the input incrementally. Days that only ever look at one line (or record) at a time can
+//! consume a `BufRead` instead of the whole input as a `&str`, so they also run over huge generated
+//! inputs or pipes without holding them in memory. The other days read their whole `Source` at
+//! once, which still lets them solve another input than the embedded one.
+//!
+//! Either way, the days get to see the input normalized (see `normalize`), unless `--strict` is
+//! given. Then an input that would need it is an error.
+
+use crate::cli::{Args, UsageError};
+use crate::normalize::{self, Checker, Issue};
+use crate::parse::{self, Input, Location, ParseError, ParseResult};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Usage(UsageError),
+    Irregular(Vec<Issue>),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Usage(e) => write!(f, "{}", e),
+            StreamError::Irregular(issues) => {
+                let issues: Vec<String> = issues.iter().map(|i| i.to_string()).collect();
+                write!(f, "the input isn't normalized: {}", issues.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+/// Where the input is read from.
+pub enum Origin {
+    Embedded(&'static str),
+    File(PathBuf),
+    Stdin,
+}
+
+pub struct Source {
+    pub origin: Origin,
+    /// Whether an input that isn't normalized is an error.
+    pub strict: bool,
+}
+
+impl Source {
+    /// `[--strict] [PATH | -]`, whatever is left of the command line after the options. Without a
+    /// path the embedded input is used, `-` reads stdin.
+    pub fn from_args(mut args: Args, embedded: &'static str) -> Result<Source, StreamError> {
+        let strict = args.flag("--strict");
+        let origin = match args.positional().map_err(StreamError::Usage)?.as_slice() {
+            [] => Origin::Embedded(embedded),
+            [path] if path == "-" => Origin::Stdin,
+            [path] => Origin::File(PathBuf::from(path)),
+            _ => {
+                return Err(StreamError::Usage(UsageError(
+                    "only one input can be given".to_string(),
+                )))
+            }
+        };
+        Ok(Source { origin, strict })
+    }
+
+    fn open_raw(&self) -> Result<Box<dyn BufRead>, StreamError> {
+        match &self.origin {
+            Origin::Embedded(input) => Ok(Box::new(input.as_bytes())),
+            Origin::File(path) => {
+                let file = File::open(path).map_err(StreamError::Io)?;
+                Ok(Box::new(BufReader::new(file)))
+            }
+            Origin::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+
+    /// Starts reading the input from the beginning. Stdin can of course only be read once.
+    ///
+    /// The records (see `lines` and `records`) of the reader come out normalized. In strict mode,
+    /// reading fails instead as soon as the reader comes across something that would need it.
+    pub fn open(&self) -> Result<Box<dyn BufRead>, StreamError> {
+        let reader = self.open_raw()?;
+        if self.strict {
+            return Ok(Box::new(Strict {
+                reader,
+                checker: Checker::new(),
+            }));
+        }
+        Ok(reader)
+    }
+
+    /// The whole input at once, normalized.
+    pub fn read(&self) -> Result<String, StreamError> {
+        self.text(self.read_bytes()?)
+    }
+
+    /// The whole input as it is, for inputs that may not be text (see `model`).
+    pub fn read_bytes(&self) -> Result<Vec<u8>, StreamError> {
+        let mut bytes = Vec::new();
+        self.open_raw()?
+            .read_to_end(&mut bytes)
+            .map_err(StreamError::Io)?;
+        Ok(bytes)
+    }
+
+    /// The text of what `read_bytes` returned, normalized like `read` does.
+    pub fn text(&self, bytes: Vec<u8>) -> Result<String, StreamError> {
+        let input = String::from_utf8(bytes)
+            .map_err(|e| StreamError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+
+        if self.strict {
+            let issues = normalize::issues(&input);
+            if !issues.is_empty() {
+                return Err(StreamError::Irregular(issues));
+            }
+            return Ok(input);
+        }
+        Ok(normalize::normalize(&input))
+    }
+}
+
+// Checks what is read for issues, and fails as soon as there's one. The rest of the input is only
+// checked then, so the error lists the same issues however the input came in. Those that can only
+// be known at the end (like a missing newline) are reported there.
+struct Strict<R> {
+    reader: R,
+    checker: Checker,
+}
+
+impl<R: BufRead> Strict<R> {
+    fn check_rest(&mut self) -> io::Result<()> {
+        loop {
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                return Ok(());
+            }
+            self.checker.feed(available);
+            let len = available.len();
+            self.reader.consume(len);
+        }
+    }
+}
+
+impl<R: BufRead> Read for Strict<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for Strict<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let issues = if self.reader.fill_buf()?.is_empty() {
+            self.checker.finish()
+        } else if self.checker.found().is_empty() {
+            Vec::new()
+        } else {
+            self.check_rest()?;
+            self.checker.finish()
+        };
+        if !issues.is_empty() {
+            let error = StreamError::Irregular(issues).to_string();
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Ok(available) = self.reader.fill_buf() {
+            self.checker.feed(&available[..amt]);
+        }
+        self.reader.consume(amt);
+    }
+}
+
+/// Command line of a streaming day: `dayN [--part 1|2] [--strict] [PATH | -]`. Without a path the embedded
+/// input is used, `-` reads stdin.
+pub struct Options {
+    pub source: Source,
+    pub part: Option<u32>,
+}
+
+impl Options {
+    pub fn from_args(embedded: &'static str) -> Result<Options, StreamError> {
+        Options::new(Args::from_env(), embedded)
+    }
+
+    /// Like `from_args`, for days that take arguments of their own: the rest of them.
+    pub fn new(mut args: Args, embedded: &'static str) -> Result<Options, StreamError> {
+        let usage = |message: &str| StreamError::Usage(UsageError(message.to_string()));
+
+        let part = args.value("--part").map_err(StreamError::Usage)?;
+        if !matches!(part, None | Some(1 | 2)) {
+            return Err(usage("--part must be 1 or 2"));
+        }
+
+        let source = Source::from_args(args, embedded)?;
+
+        if let (Origin::Stdin, None) = (&source.origin, part) {
+            // Both parts would need their own pass over the input.
+            return Err(usage(
+                "reading stdin needs --part, it can only be read once",
+            ));
+        }
+
+        Ok(Options { source, part })
+    }
+
+    pub fn runs(&self, part: u32) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// A line, or a part of a line up to a delimiter, together with where it starts in the input.
+pub struct Record {
+    pub text: String,
+    pub start: Location,
+}
+
+impl Record {
+    /// Runs the parser on the whole record. Errors are located in the complete input.
+    pub fn parse<'a, T, P>(&'a self, parser: P) -> Result<T, ParseError>
+    where
+        P: Fn(Input<'a>) -> ParseResult<'a, T>,
+    {
+        parse::parse(parser, &self.text).map_err(|e| e.relative_to(self.start))
+    }
+}
+
+/// Iterator over the records of a reader, see `lines` and `records`.
+pub struct Records<R> {
+    reader: R,
+    delimiter: u8,
+    location: Location,
+}
+
+/// The lines of the reader, one at a time. Blank lines are skipped. Like the whole input, lines
+/// are normalized: without byte order mark, carriage return or blanks at their end.
+pub fn lines<R: BufRead>(reader: R) -> Records<R> {
+    records(reader, b'\n')
+}
+
+/// The records of the reader, ending at the delimiter or at the end of a line, one at a time.
+/// Blank lines are skipped.
+pub fn records<R: BufRead>(reader: R, delimiter: u8) -> Records<R> {
+    Records {
+        reader,
+        delimiter,
+        location: Location { line: 1, column: 1 },
+    }
+}
+
+impl<R: BufRead> Records<R> {
+    // Reads up to (and consumes) the next delimiter or newline. Returns the bytes before it and
+    // the separator that was found, or `None` at the end of the input.
+    fn read_record(&mut self) -> io::Result<(Vec<u8>, Option<u8>)> {
+        let mut bytes = Vec::new();
+        loop {
+            let available = self.reader.fill_buf()?;
+            if available.is_empty() {
+                return Ok((bytes, None));
+            }
+
+            let delimiter = self.delimiter;
+            match available.iter().position(|&b| b == b'\n' || b == delimiter) {
+                Some(index) => {
+                    let separator = available[index];
+                    bytes.extend_from_slice(&available[..index]);
+                    self.reader.consume(index + 1);
+                    return Ok((bytes, Some(separator)));
+                }
+                None => {
+                    let len = available.len();
+                    bytes.extend_from_slice(available);
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = Result<Record, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (mut bytes, separator) = match self.read_record() {
+                Ok(record) => record,
+                Err(e) => return Some(Err(StreamError::Io(e))),
+            };
+            // Separators other than the delimiter are newlines (or the end of the input).
+            let ends_line = separator != Some(self.delimiter) || self.delimiter == b'\n';
+            while ends_line && matches!(bytes.last(), Some(b' ' | b'\t' | b'\r')) {
+                bytes.pop();
+            }
+            if self.location == (Location { line: 1, column: 1 }) {
+                while let Some(rest) = bytes.strip_prefix("\u{feff}".as_bytes()) {
+                    bytes = rest.to_vec();
+                }
+            }
+
+            let start = self.location;
+            let text = match String::from_utf8(bytes) {
+                Ok(text) => text,
+                Err(e) => {
+                    let e = io::Error::new(io::ErrorKind::InvalidData, e);
+                    return Some(Err(StreamError::Io(e)));
+                }
+            };
+
+            if ends_line {
+                self.location = Location {
+                    line: start.line + 1,
+                    column: 1,
+                };
+            } else {
+                self.location.column += text.chars().count() + 1;
+            }
+
+            if text.is_empty() && ends_line && start.column == 1 {
+                if separator.is_some() {
+                    continue;
+                }
+                return None;
+            }
+
+            return Some(Ok(Record { text, start }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_strict(text: &str, chunk: usize) -> Result<Vec<u8>, String> {
+        let mut strict = Strict {
+            reader: BufReader::with_capacity(chunk, text.as_bytes()),
+            checker: Checker::new(),
+        };
+        let mut bytes = Vec::new();
+        match strict.read_to_end(&mut bytes) {
+            Ok(_) => Ok(bytes),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    #[test]
+    fn strict_reading_finds_the_same_issues_in_any_chunks() {
+        for text in normalize::texts(5) {
+            let issues = normalize::issues(&text);
+            let expected = if issues.is_empty() {
+                Ok(text.as_bytes().to_vec())
+            } else {
+                Err(StreamError::Irregular(issues).to_string())
+            };
+            for chunk in [1, 2, 3, 8192] {
+                assert_eq!(
+                    read_strict(&text, chunk),
+                    expected,
+                    "{:?} in {}",
+                    text,
+                    chunk
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn strict_sources_fail() {
+        let source = Source {
+            origin: Origin::Embedded("a \r\nb"),
+            strict: true,
+        };
+        let error = source.read().unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "the input isn't normalized: carriage returns (CRLF) on 1 line(s), the first is line 1, \
+             blanks at the end of 1 line(s), the first is line 1, the last line has no newline"
+        );
+        assert!(source.open().unwrap().lines().any(|l| l.is_err()));
+    }
+}
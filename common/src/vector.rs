@@ -0,0 +1,474 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// Number types usable as vector components.
+pub trait Scalar:
+    Copy + PartialOrd + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! impl_scalar_int {
+    ($($t:ty),*) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl Scalar for f64 {
+    const ZERO: Self = 0.0;
+    const ONE: Self = 1.0;
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(self + rhs)
+    }
+
+    fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(self - rhs)
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseVectorError {
+    WrongComponentCount(String),
+    InvalidComponent(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec2<T> {
+    pub const fn new(x: T, y: T) -> Vec2<T> {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> Vec3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Vec3<T> {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T: Scalar> Vec2<T> {
+    pub fn manhattan(&self, other: &Vec2<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn dot(&self, other: &Vec2<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The z component of the cross product of the two vectors extended to 3D.
+    pub fn cross(&self, other: &Vec2<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// `None` if the addition overflows, e.g. for unsigned coordinates.
+    pub fn checked_add(&self, other: Vec2<T>) -> Option<Vec2<T>> {
+        Some(Vec2::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+        ))
+    }
+
+    /// `None` if the subtraction overflows, e.g. when going below 0 for unsigned coordinates.
+    pub fn checked_sub(&self, other: Vec2<T>) -> Option<Vec2<T>> {
+        Some(Vec2::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+        ))
+    }
+
+    /// Whether the vector lies in the rectangle from (0, 0) (inclusive) to `size` (exclusive).
+    pub fn is_within(&self, size: Vec2<T>) -> bool {
+        self.x >= T::ZERO && self.y >= T::ZERO && self.x < size.x && self.y < size.y
+    }
+
+    /// The orthogonal neighbours (north, west, south, east, with north being negative y) that
+    /// don't overflow.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Vec2<T>> {
+        let one = T::ONE;
+        let zero = T::ZERO;
+        [
+            self.checked_sub(Vec2::new(zero, one)),
+            self.checked_sub(Vec2::new(one, zero)),
+            self.checked_add(Vec2::new(zero, one)),
+            self.checked_add(Vec2::new(one, zero)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// The orthogonal and diagonal neighbours that don't overflow.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Vec2<T>> {
+        let this = *self;
+        let one = T::ONE;
+        let rows = [
+            this.y.checked_sub(one),
+            Some(this.y),
+            this.y.checked_add(one),
+        ];
+        let columns = [
+            this.x.checked_sub(one),
+            Some(this.x),
+            this.x.checked_add(one),
+        ];
+
+        rows.into_iter()
+            .flatten()
+            .flat_map(move |y| columns.into_iter().flatten().map(move |x| Vec2::new(x, y)))
+            .filter(move |v| *v != this)
+    }
+}
+
+impl<T: Scalar> Vec3<T> {
+    pub fn manhattan(&self, other: &Vec3<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn dot(&self, other: &Vec3<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Vec3<T>) -> Vec3<T> {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn checked_add(&self, other: Vec3<T>) -> Option<Vec3<T>> {
+        Some(Vec3::new(
+            self.x.checked_add(other.x)?,
+            self.y.checked_add(other.y)?,
+            self.z.checked_add(other.z)?,
+        ))
+    }
+
+    pub fn checked_sub(&self, other: Vec3<T>) -> Option<Vec3<T>> {
+        Some(Vec3::new(
+            self.x.checked_sub(other.x)?,
+            self.y.checked_sub(other.y)?,
+            self.z.checked_sub(other.z)?,
+        ))
+    }
+
+    /// The six neighbours sharing a face that don't overflow.
+    pub fn neighbours6(&self) -> impl Iterator<Item = Vec3<T>> {
+        let one = T::ONE;
+        let zero = T::ZERO;
+        let deltas = [
+            Vec3::new(one, zero, zero),
+            Vec3::new(zero, one, zero),
+            Vec3::new(zero, zero, one),
+        ];
+        let this = *self;
+        deltas
+            .into_iter()
+            .flat_map(move |d| [this.checked_sub(d), this.checked_add(d)])
+            .flatten()
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec2<T> {
+    type Output = Vec2<T>;
+
+    fn neg(self) -> Self::Output {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T: Add<Output = T>> Add for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Vec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Vec3<T> {
+    type Output = Vec3<T>;
+
+    fn neg(self) -> Self::Output {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Vec3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+        self.z += rhs.z;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+        self.z -= rhs.z;
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from(value: (T, T)) -> Self {
+        Vec2::new(value.0, value.1)
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from(value: (T, T, T)) -> Self {
+        Vec3::new(value.0, value.1, value.2)
+    }
+}
+
+impl<T: Display> Display for Vec2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {}", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Vec3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, {}, {}", self.x, self.y, self.z)
+    }
+}
+
+// Parses comma separated components, surrounding whitespace is ignored.
+fn parse_components<T: FromStr, const N: usize>(input: &str) -> Result<[T; N], ParseVectorError> {
+    let components: Vec<T> = input
+        .split(',')
+        .map(|s| {
+            s.trim()
+                .parse()
+                .map_err(|_| ParseVectorError::InvalidComponent(s.to_string()))
+        })
+        .collect::<Result<_, _>>()?;
+
+    components
+        .try_into()
+        .map_err(|_| ParseVectorError::WrongComponentCount(input.to_string()))
+}
+
+/// Parses "x,y".
+impl<T: FromStr> FromStr for Vec2<T> {
+    type Err = ParseVectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_components(s)?;
+        Ok(Vec2 { x, y })
+    }
+}
+
+/// Parses "x,y,z".
+impl<T: FromStr> FromStr for Vec3<T> {
+    type Err = ParseVectorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = parse_components(s)?;
+        Ok(Vec3 { x, y, z })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators() {
+        let mut a = Vec2::new(3, -2);
+        let b = Vec2::new(1, 5);
+        assert_eq!(a + b, Vec2::new(4, 3));
+        assert_eq!(a - b, Vec2::new(2, -7));
+        assert_eq!(a * 3, Vec2::new(9, -6));
+        assert_eq!(-a, Vec2::new(-3, 2));
+        a += b;
+        assert_eq!(a, Vec2::new(4, 3));
+        a -= b;
+        assert_eq!(a, Vec2::new(3, -2));
+
+        let c = Vec3::new(1, 2, 3);
+        assert_eq!(c + c * 2 - Vec3::new(1, 1, 1), Vec3::new(2, 5, 8));
+        assert_eq!(-c, Vec3::new(-1, -2, -3));
+    }
+
+    #[test]
+    fn products_and_distances() {
+        assert_eq!(Vec2::new(3, -2).manhattan(&Vec2::new(-1, 5)), 11);
+        assert_eq!(Vec2::new(2u32, 7).manhattan(&Vec2::new(5, 1)), 9);
+        assert_eq!(Vec3::new(1, 2, 3).manhattan(&Vec3::new(3, 2, 1)), 4);
+
+        assert_eq!(Vec2::new(1, 0).cross(&Vec2::new(0, 1)), 1);
+        assert_eq!(Vec2::new(0, 1).cross(&Vec2::new(1, 0)), -1);
+        assert_eq!(Vec2::new(2, 4).cross(&Vec2::new(1, 2)), 0);
+        assert_eq!(
+            Vec3::new(1, 0, 0).cross(&Vec3::new(0, 1, 0)),
+            Vec3::new(0, 0, 1)
+        );
+        assert_eq!(Vec3::new(1, 2, 3).dot(&Vec3::new(4, 5, 6)), 32);
+    }
+
+    #[test]
+    fn neighbours_in_order() {
+        let n4: Vec<_> = Vec2::new(5, 5).neighbours4().collect();
+        assert_eq!(
+            n4,
+            [
+                Vec2::new(5, 4),
+                Vec2::new(4, 5),
+                Vec2::new(5, 6),
+                Vec2::new(6, 5)
+            ]
+        );
+        // Unsigned coordinates don't go below 0.
+        let n4: Vec<_> = Vec2::new(0usize, 0).neighbours4().collect();
+        assert_eq!(n4, [Vec2::new(0, 1), Vec2::new(1, 0)]);
+
+        let n8: Vec<_> = Vec2::new(1, 1).neighbours8().collect();
+        assert_eq!(
+            n8,
+            [
+                Vec2::new(0, 0),
+                Vec2::new(1, 0),
+                Vec2::new(2, 0),
+                Vec2::new(0, 1),
+                Vec2::new(2, 1),
+                Vec2::new(0, 2),
+                Vec2::new(1, 2),
+                Vec2::new(2, 2)
+            ]
+        );
+        assert_eq!(Vec2::new(0u8, 255).neighbours8().count(), 3);
+
+        let n6: Vec<_> = Vec3::new(1, 1, 1).neighbours6().collect();
+        assert_eq!(
+            n6,
+            [
+                Vec3::new(0, 1, 1),
+                Vec3::new(2, 1, 1),
+                Vec3::new(1, 0, 1),
+                Vec3::new(1, 2, 1),
+                Vec3::new(1, 1, 0),
+                Vec3::new(1, 1, 2)
+            ]
+        );
+        assert_eq!(Vec3::new(0u32, 0, 0).neighbours6().count(), 3);
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!("3, -4".parse::<Vec2<i32>>().unwrap(), Vec2::new(3, -4));
+        assert_eq!(" 1,2 , 3".parse::<Vec3<i64>>().unwrap(), Vec3::new(1, 2, 3));
+
+        assert!(matches!(
+            "1, x".parse::<Vec2<i32>>(),
+            Err(ParseVectorError::InvalidComponent(c)) if c == " x"
+        ));
+        assert!(matches!(
+            "-1,2".parse::<Vec2<u32>>(),
+            Err(ParseVectorError::InvalidComponent(c)) if c == "-1"
+        ));
+        assert!(matches!(
+            "1,2,3".parse::<Vec2<i32>>(),
+            Err(ParseVectorError::WrongComponentCount(s)) if s == "1,2,3"
+        ));
+        assert!(matches!(
+            "1,2".parse::<Vec3<i32>>(),
+            Err(ParseVectorError::WrongComponentCount(_))
+        ));
+    }
+}
@@ -0,0 +1,95 @@
+//! Answers of earlier runs, so slow days don't have to be solved again just to print them. An
+//! entry belongs to a day and the hash of an input, and remembers the hash of the binary that found
+//! the answers. Whatever changes the binary (the day's code, the common library, the features)
+//! invalidates the entry.
+
+use crate::day::{Answer, Day};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// FNV-1a. Unlike `DefaultHasher`, it's guaranteed to stay the same between Rust versions.
+pub fn hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: PathBuf) -> Cache {
+        Cache { dir }
+    }
+
+    fn path(&self, day: &Day, input: u64) -> PathBuf {
+        self.dir
+            .join(format!("{}-day{}-{:016x}.txt", day.year, day.number, input))
+    }
+
+    /// The answers for the input, if they were found by the same build.
+    pub fn get(&self, day: &Day, input: u64, build: u64) -> Option<Vec<Answer>> {
+        let entry = fs::read_to_string(self.path(day, input)).ok()?;
+        let mut lines = entry.lines();
+        if lines.next()? != format!("build {:016x}", build) {
+            return None;
+        }
+
+        let answers: Vec<Answer> = lines.map(Answer::parse).collect::<Option<_>>()?;
+        // Entries without answers were only ever stored by older runners.
+        if answers.is_empty() {
+            return None;
+        }
+        Some(answers)
+    }
+
+    /// Stores the answers, replacing those of an older build. A run without answers (the day
+    /// printed none, or none that could be parsed) isn't stored, so the day runs again next time.
+    pub fn put(&self, day: &Day, input: u64, build: u64, answers: &[Answer]) -> io::Result<()> {
+        if answers.is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir)?;
+
+        let mut entry = format!("build {:016x}\n", build);
+        for answer in answers {
+            entry += &format!("{}\n", answer);
+        }
+        fs::write(self.path(day, input), entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_runs_with_answers_are_kept() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let cache = Cache::new(dir.clone());
+        let day = Day::new(&dir, 2099, 3);
+
+        cache.put(&day, 1, 7, &[]).unwrap();
+        assert!(cache.get(&day, 1, 7).is_none());
+
+        let answers = [Answer::parse("Part 1: 42").unwrap()];
+        cache.put(&day, 1, 7, &answers).unwrap();
+        let cached = cache.get(&day, 1, 7).unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].to_string(), "Part 1: 42");
+        // Another build, or another input.
+        assert!(cache.get(&day, 1, 8).is_none());
+        assert!(cache.get(&day, 2, 7).is_none());
+
+        // An empty entry stored before runs without answers were skipped.
+        fs::write(cache.path(&day, 3), format!("build {:016x}\n", 7)).unwrap();
+        assert!(cache.get(&day, 3, 7).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,163 @@
+//! Runs the solutions of a day side by side: the day's own binary and its external solutions, on
+//! the same input and several times each. The answers and best runtimes end up in a table.
+
+use crate::day::{self, Answer, Day, DayError};
+use crate::external::External;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub struct Row {
+    pub name: String,
+    pub answers: Vec<Answer>,
+    pub best: Duration,
+}
+
+// Runs a solution a number of times, keeping the answers of the first run and the best runtime.
+// The runtime includes starting the process, which is the same for every kind of solution.
+fn measure<F>(name: &str, runs: u32, solve: F) -> Result<Row, DayError>
+where
+    F: Fn() -> Result<Vec<Answer>, DayError>,
+{
+    let mut answers = None;
+    let mut best = Duration::MAX;
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let result = solve()?;
+        best = best.min(start.elapsed());
+        answers.get_or_insert(result);
+    }
+
+    Ok(Row {
+        name: name.to_string(),
+        answers: answers.unwrap(),
+        best,
+    })
+}
+
+/// Runs the day and its external solutions on the input. Solutions that can't run on it (not built,
+/// or built with another input) are left out with a note.
+pub fn compare(
+    day: &Day,
+    externals: &[External],
+    input: &Path,
+    runs: u32,
+) -> Result<Vec<Row>, DayError> {
+    let binary = day.build()?;
+    let mut rows = vec![measure("rust", runs, || Day::run(&binary, input))?];
+
+    for external in externals.iter().filter(|e| e.day == day.number) {
+        if !external.is_available() {
+            println!("Skipped {}: not built", external.name);
+        } else if external.embeds_input() && input != day.default_input() {
+            println!(
+                "Skipped {}: it only solves the default input",
+                external.name
+            );
+        } else {
+            rows.push(measure(&external.name, runs, || external.run(input))?);
+        }
+    }
+
+    Ok(rows)
+}
+
+// The parts any of the solutions answers.
+fn parts(rows: &[Row]) -> Vec<u32> {
+    let mut parts: Vec<u32> = rows
+        .iter()
+        .flat_map(|r| r.answers.iter().map(|a| a.part))
+        .collect();
+    parts.sort();
+    parts.dedup();
+    parts
+}
+
+// The answer of the solution to the part, as in the table.
+fn value(row: &Row, part: u32) -> Option<&str> {
+    day::answer_of(&row.answers, part).map(|a| a.value.as_str())
+}
+
+/// Whether all solutions found the same answers, as shown in the table. A part that a solution
+/// doesn't answer counts as a disagreement.
+pub fn agree(rows: &[Row]) -> bool {
+    parts(rows)
+        .into_iter()
+        .all(|part| rows.iter().all(|r| value(r, part) == value(&rows[0], part)))
+}
+
+/// Prints the answers and the best runtimes as a Markdown table.
+pub fn print_table(rows: &[Row], runs: u32) {
+    let parts = parts(rows);
+
+    let mut header = vec!["Solution".to_string()];
+    header.extend(parts.iter().map(|p| format!("Part {}", p)));
+    header.push(format!("Best of {}", runs.max(1)));
+
+    let mut table = vec![header];
+    for row in rows {
+        let mut cells = vec![row.name.clone()];
+        for part in &parts {
+            cells.push(value(row, *part).unwrap_or("-").to_string());
+        }
+        cells.push(format!("{:.2?}", row.best));
+        table.push(cells);
+    }
+
+    print_markdown(&table);
+}
+
+/// Prints the rows as a Markdown table, the first one being the header.
+pub fn print_markdown(table: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..table[0].len())
+        .map(|c| table.iter().map(|r| r[c].chars().count()).max().unwrap())
+        .collect();
+    let line = |cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {:width$} ", cell, width = width))
+            .collect();
+        println!("|{}|", cells.join("|"));
+    };
+
+    line(&table[0]);
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    line(&rule);
+    for row in &table[1..] {
+        line(row);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(answers: &[(u32, &str)]) -> Row {
+        Row {
+            name: "test".to_string(),
+            answers: answers
+                .iter()
+                .map(|&(part, value)| Answer {
+                    part,
+                    value: value.to_string(),
+                })
+                .collect(),
+            best: Duration::ZERO,
+        }
+    }
+
+    #[test]
+    fn agreement_is_per_part() {
+        let rust = row(&[(1, "3"), (2, "4")]);
+        assert!(agree(&[rust, row(&[(2, "4"), (1, "3")])]));
+
+        // Like day 25, which prints part 1 twice. Only the last one counts.
+        let rust = row(&[(1, "1458"), (1, "54")]);
+        assert!(agree(&[rust, row(&[(1, "54")])]));
+
+        let rust = row(&[(1, "3"), (2, "4")]);
+        assert!(!agree(&[rust, row(&[(1, "3"), (2, "5")])]));
+        let rust = row(&[(1, "3"), (2, "4")]);
+        assert!(!agree(&[rust, row(&[(1, "3")])]));
+    }
+}
@@ -0,0 +1,302 @@
+//! The days are crates of their own. The runner builds their binaries and runs them on an input
+//! file, picking the answers out of what they print.
+
+use aoc_common::parse::{self, integer, pair, preceded, rest_of_line, tag, terminated};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum DayError {
+    Io(io::Error),
+    BuildFailed(String),
+    Failed(String),
+    InvalidExternal(String),
+    InvalidEntry(String),
+    OutOfTime(Duration),
+}
+
+impl Display for DayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DayError::Io(e) => write!(f, "{}", e),
+            DayError::BuildFailed(stderr) => write!(f, "the build failed:\n{}", stderr),
+            DayError::Failed(stderr) => write!(f, "{}", stderr),
+            DayError::InvalidExternal(line) => write!(f, "invalid external day: {}", line),
+            DayError::InvalidEntry(e) => write!(f, "invalid registry entry {}", e),
+            DayError::OutOfTime(budget) => write!(f, "out of time after {:.2?}", budget),
+        }
+    }
+}
+
+impl std::error::Error for DayError {}
+
+/// A line "Part N: answer", as printed by the days.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub part: u32,
+    pub value: String,
+}
+
+impl Answer {
+    pub fn parse(line: &str) -> Option<Answer> {
+        let part = preceded(tag("Part "), terminated(integer(), tag(": ")));
+        let (part, value) = parse::parse(pair(part, rest_of_line()), line).ok()?;
+        Some(Answer {
+            part,
+            value: value.to_string(),
+        })
+    }
+}
+
+/// The answer of the part: the last one if a day prints several. Day 25 first prints part 1 as
+/// cut for the author's input, and then as found for any input.
+pub fn answer_of(answers: &[Answer], part: u32) -> Option<&Answer> {
+    answers.iter().rev().find(|a| a.part == part)
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Part {}: {}", self.part, self.value)
+    }
+}
+
+pub struct Day {
+    pub year: u32,
+    pub number: u32,
+    dir: PathBuf,
+    target_dir: PathBuf,
+}
+
+/// The event years, the directories of the repository named like one.
+pub fn years(root: &Path) -> io::Result<Vec<u32>> {
+    let mut years = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(year) = entry.file_name().to_str().and_then(|n| n.parse().ok()) {
+            years.push(year);
+        }
+    }
+
+    years.sort();
+    Ok(years)
+}
+
+impl Day {
+    /// The days of a year share a target directory, so the common library is only built once. The
+    /// years need their own, as their binaries have the same names.
+    pub fn new(root: &Path, year: u32, number: u32) -> Day {
+        Day {
+            year,
+            number,
+            dir: root.join(year.to_string()).join(format!("day{}", number)),
+            target_dir: root.join("target").join(year.to_string()),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The directory of the year, which lists its external solutions.
+    pub fn year_dir(&self) -> &Path {
+        self.dir.parent().unwrap()
+    }
+
+    pub fn exists(&self) -> bool {
+        self.dir.join("Cargo.toml").is_file()
+    }
+
+    /// The input that is embedded into the binary.
+    pub fn default_input(&self) -> PathBuf {
+        self.dir.join("rsc").join("input.txt")
+    }
+
+    /// Builds the release binary (if it's not up to date) and returns its path.
+    pub fn build(&self) -> Result<PathBuf, DayError> {
+        let output = Command::new("cargo")
+            .args(["build", "--release", "--quiet", "--manifest-path"])
+            .arg(self.dir.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(&self.target_dir)
+            .output()
+            .map_err(DayError::Io)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(DayError::BuildFailed(stderr.trim().to_string()));
+        }
+
+        Ok(self
+            .target_dir
+            .join("release")
+            .join(format!("day{}", self.number)))
+    }
+
+    /// Runs the binary on the input file and returns the answers it printed.
+    pub fn run(binary: &Path, input: &Path) -> Result<Vec<Answer>, DayError> {
+        let output = Command::new(binary)
+            .arg(input)
+            .output()
+            .map_err(DayError::Io)?;
+        answers(output)
+    }
+
+    /// Like `run`, but kills the binary if it takes longer than the budget.
+    pub fn run_within(
+        binary: &Path,
+        input: &Path,
+        budget: Duration,
+    ) -> Result<Vec<Answer>, DayError> {
+        answers(output_within(Command::new(binary).arg(input), budget)?)
+    }
+}
+
+type Pipe = thread::JoinHandle<io::Result<Vec<u8>>>;
+
+// Starts the command with its output piped. The pipes are read while waiting for the child,
+// otherwise a chatty child would block once they're full.
+fn spawn_piped(command: &mut Command) -> Result<(Child, Pipe, Pipe), DayError> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(DayError::Io)?;
+
+    let mut stdout = child.stdout.take().unwrap();
+    let mut stderr = child.stderr.take().unwrap();
+    let stdout = thread::spawn(move || {
+        let mut buffer = Vec::new();
+        io::Read::read_to_end(&mut stdout, &mut buffer).map(|_| buffer)
+    });
+    let stderr = thread::spawn(move || {
+        let mut buffer = Vec::new();
+        io::Read::read_to_end(&mut stderr, &mut buffer).map(|_| buffer)
+    });
+    Ok((child, stdout, stderr))
+}
+
+/// Runs the command like `Command::output`, unless it takes longer than the budget.
+pub fn output_within(command: &mut Command, budget: Duration) -> Result<Output, DayError> {
+    let (mut child, stdout, stderr) = spawn_piped(command)?;
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(DayError::Io)? {
+            break status;
+        }
+        if start.elapsed() > budget {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(DayError::OutOfTime(budget));
+        }
+        thread::sleep(Duration::from_millis(1));
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap().map_err(DayError::Io)?,
+        stderr: stderr.join().unwrap().map_err(DayError::Io)?,
+    })
+}
+
+/// Runs the command like `Command::output`, and also returns the most memory it used at once (its
+/// peak resident set size) in bytes.
+#[cfg(unix)]
+pub fn output_with_memory(command: &mut Command) -> Result<(Output, Option<u64>), DayError> {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    let (child, stdout, stderr) = spawn_piped(command)?;
+
+    // `Child::wait` doesn't tell how much memory the child used, `wait4` does.
+    let mut status = 0;
+    let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+    let pid = child.id() as libc::pid_t;
+    while unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } < 0 {
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(DayError::Io(error));
+        }
+    }
+    // Kilobytes on Linux, bytes on macOS.
+    let unit = if cfg!(target_os = "macos") { 1 } else { 1024 };
+
+    let output = Output {
+        status: ExitStatus::from_raw(status),
+        stdout: stdout.join().unwrap().map_err(DayError::Io)?,
+        stderr: stderr.join().unwrap().map_err(DayError::Io)?,
+    };
+    Ok((output, Some(usage.ru_maxrss as u64 * unit)))
+}
+
+/// Elsewhere, the memory is unknown.
+#[cfg(not(unix))]
+pub fn output_with_memory(command: &mut Command) -> Result<(Output, Option<u64>), DayError> {
+    let output = command.stdin(Stdio::null()).output();
+    Ok((output.map_err(DayError::Io)?, None))
+}
+
+/// The answers a solution printed, or what it complained about if it failed.
+pub fn answers(output: Output) -> Result<Vec<Answer>, DayError> {
+    if !output.status.success() {
+        // The days return their errors from main, which prints them to stderr.
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DayError::Failed(stderr.trim().to_string()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout.lines().filter_map(Answer::parse).collect())
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Day {} of {}", self.number, self.year)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_within_the_budget() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo 'Part 1: 3'"]);
+        let output = output_within(&mut command, Duration::from_secs(10)).unwrap();
+        let answers: Vec<String> = answers(output)
+            .unwrap()
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        assert_eq!(answers, ["Part 1: 3"]);
+    }
+
+    #[test]
+    fn kills_what_runs_out_of_time() {
+        let start = Instant::now();
+        let mut command = Command::new("sleep");
+        command.arg("10");
+        let result = output_within(&mut command, Duration::from_millis(100));
+        assert!(matches!(result, Err(DayError::OutOfTime(_))));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn the_last_answer_of_a_part_counts() {
+        let mut command = Command::new("sh");
+        command.args(["-c", "printf 'Part 1: 1458\\nPart 2: 7\\nPart 1: 54\\n'"]);
+        let output = output_within(&mut command, Duration::from_secs(10)).unwrap();
+        let answers = answers(output).unwrap();
+        assert_eq!(answer_of(&answers, 1).unwrap().value, "54");
+        assert_eq!(answer_of(&answers, 2).unwrap().value, "7");
+        assert!(answer_of(&answers, 3).is_none());
+    }
+}
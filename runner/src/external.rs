@@ -0,0 +1,160 @@
+//! Solutions that aren't crates of this repository, like the ARM64 ports. A year lists them in its
+//! `external.txt`, with a line "dayN name command [arguments]" each. A program with a path is
+//! relative to the year directory, otherwise it's searched for in the `PATH`. Like the days, an
+//! external solution has to print its answers as "Part N: answer" lines.
+
+use crate::day::{self, Answer, DayError};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Replaced by the path of the input in the arguments of a command.
+pub const INPUT: &str = "{input}";
+
+#[derive(Debug)]
+pub struct External {
+    pub day: u32,
+    pub name: String,
+    program: PathBuf,
+    args: Vec<String>,
+}
+
+impl External {
+    // "day16 arm64 ARM64/day16/day16"
+    fn parse(year_dir: &Path, line: &str) -> Option<External> {
+        let mut words = line.split_whitespace();
+        let day = words.next()?.strip_prefix("day")?.parse().ok()?;
+        let name = words.next()?.to_string();
+        let program = words.next()?;
+        let program = if program.contains('/') {
+            year_dir.join(program)
+        } else {
+            PathBuf::from(program)
+        };
+
+        Some(External {
+            day,
+            name,
+            program,
+            args: words.map(|w| w.to_string()).collect(),
+        })
+    }
+
+    /// The external solutions of a year, none if it doesn't list any. Empty lines and lines
+    /// starting with '#' are ignored.
+    pub fn load(year_dir: &Path) -> Result<Vec<External>, DayError> {
+        let list = match fs::read_to_string(year_dir.join("external.txt")) {
+            Ok(list) => list,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(DayError::Io(e)),
+        };
+
+        list.lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(|l| {
+                External::parse(year_dir, l).ok_or_else(|| DayError::InvalidExternal(l.to_string()))
+            })
+            .collect()
+    }
+
+    /// Whether the program can be run. The ARM64 ports, for example, only build on macOS.
+    pub fn is_available(&self) -> bool {
+        self.program.components().count() == 1 || self.program.is_file()
+    }
+
+    /// Whether the solution has its input built in (like the ARM64 ports) instead of reading the one
+    /// it's given. It can only be compared on the default input of the day then.
+    pub fn embeds_input(&self) -> bool {
+        !self.args.iter().any(|a| a.contains(INPUT))
+    }
+
+    /// Runs the command on the input file and returns the answers it printed.
+    pub fn run(&self, input: &Path) -> Result<Vec<Answer>, DayError> {
+        let input = input.to_string_lossy();
+        let output = Command::new(&self.program)
+            .args(self.args.iter().map(|a| a.replace(INPUT, &input)))
+            .output()
+            .map_err(DayError::Io)?;
+        day::answers(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    // A fresh directory for a test's year, with an executable stub "stub.sh" in it.
+    fn year_dir(test: &str, stub: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("aoc-external-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let script = dir.join("stub.sh");
+        fs::write(&script, stub).unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        dir
+    }
+
+    #[test]
+    fn runs_a_stub_on_the_input() {
+        // Answers with the number of lines of the input, and noise that isn't an answer.
+        let dir = year_dir(
+            "input",
+            "#!/bin/sh\necho \"Part 1: $(wc -l < \"$1\" | tr -d ' ')\"\necho 'Elapsed: 1ms'\necho 'Part 2: stub'\n",
+        );
+        fs::write(
+            dir.join("external.txt"),
+            "# A stub.\n\nday3 stub ./stub.sh {input}\n",
+        )
+        .unwrap();
+        fs::write(dir.join("input.txt"), "a\nb\nc\n").unwrap();
+
+        let externals = External::load(&dir).unwrap();
+        assert_eq!(externals.len(), 1);
+        assert_eq!(externals[0].day, 3);
+        assert_eq!(externals[0].name, "stub");
+        assert!(externals[0].is_available());
+        assert!(!externals[0].embeds_input());
+
+        let answers = externals[0].run(&dir.join("input.txt")).unwrap();
+        let answers: Vec<String> = answers.iter().map(|a| a.to_string()).collect();
+        assert_eq!(answers, ["Part 1: 3", "Part 2: stub"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reports_a_failing_stub() {
+        let dir = year_dir("failing", "#!/bin/sh\necho 'no input' >&2\nexit 1\n");
+        fs::write(dir.join("external.txt"), "day3 stub ./stub.sh\n").unwrap();
+
+        let externals = External::load(&dir).unwrap();
+        assert!(externals[0].embeds_input());
+        match externals[0].run(&dir.join("input.txt")) {
+            Err(DayError::Failed(stderr)) => assert_eq!(stderr, "no input"),
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rejects_invalid_lines_and_missing_programs() {
+        let dir = year_dir("invalid", "");
+        fs::write(dir.join("external.txt"), "3 stub ./stub.sh\n").unwrap();
+        assert!(matches!(
+            External::load(&dir),
+            Err(DayError::InvalidExternal(line)) if line == "3 stub ./stub.sh"
+        ));
+
+        fs::write(dir.join("external.txt"), "day3 arm64 ARM64/day3/day3\n").unwrap();
+        assert!(!External::load(&dir).unwrap()[0].is_available());
+
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(External::load(&dir).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,501 @@
+//! How the runtimes and memory of the days change over the commits of the repository. `aoc bench`
+//! measures days and appends the results to a history file, along with the commit they were
+//! measured at and its date. `aoc history` shows them commit by commit, and points out the commits
+//! that made a part noticeably slower or faster.
+//!
+//! The history file has a line per part and run of `aoc bench`, with tab-separated fields:
+//!
+//! ```text
+//! commit  date  year  day  input  part  nanoseconds  bytes
+//! ```
+//!
+//! The commit has "-dirty" appended if there were uncommitted changes. The input is its hash, so
+//! only runs on the same input are compared. The memory is the peak of the whole run (both parts),
+//! or "-" where it can't be measured.
+
+use crate::compare;
+use crate::day::{self, Answer, Day, DayError};
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// A change smaller than this is never noticeable, whatever the ratio: short runtimes vary too much
+/// from run to run.
+const MIN_CHANGE: Duration = Duration::from_micros(100);
+
+pub struct Commit {
+    pub id: String,
+    pub date: String,
+}
+
+/// The commit checked out in the repository at `root`.
+pub fn current_commit(root: &Path) -> io::Result<Commit> {
+    let git = |args: &[&str]| -> io::Result<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(args)
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = format!("git {}: {}", args.join(" "), stderr.trim());
+            return Err(io::Error::other(message));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let log = git(&["log", "-1", "--format=%H %cI"])?;
+    let Some((id, date)) = log.split_once(' ') else {
+        return Err(io::Error::other(format!("unexpected git log: {}", log)));
+    };
+    let dirty = !git(&["status", "--porcelain", "--untracked-files=no"])?.is_empty();
+    Ok(Commit {
+        id: if dirty {
+            format!("{}-dirty", id)
+        } else {
+            id.to_string()
+        },
+        date: date.to_string(),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub commit: String,
+    /// The date of the commit, not of the measurement, in ISO 8601.
+    pub date: String,
+    pub year: u32,
+    pub day: u32,
+    pub input: u64,
+    pub part: u32,
+    pub runtime: Duration,
+    pub memory: Option<u64>,
+}
+
+impl Record {
+    fn parse(line: &str) -> Option<Record> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [commit, date, year, day, input, part, runtime, memory] = fields[..] else {
+            return None;
+        };
+        Some(Record {
+            commit: commit.to_string(),
+            date: date.to_string(),
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            input: u64::from_str_radix(input, 16).ok()?,
+            part: part.parse().ok()?,
+            runtime: Duration::from_nanos(runtime.parse().ok()?),
+            memory: match memory {
+                "-" => None,
+                memory => Some(memory.parse().ok()?),
+            },
+        })
+    }
+
+    // The commit, abbreviated like git does.
+    fn short_commit(&self) -> String {
+        match self.commit.strip_suffix("-dirty") {
+            Some(id) => format!("{:.7}-dirty", id),
+            None => format!("{:.7}", self.commit),
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let memory = self.memory.map_or("-".to_string(), |m| m.to_string());
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{:016x}\t{}\t{}\t{}",
+            self.commit,
+            self.date,
+            self.year,
+            self.day,
+            self.input,
+            self.part,
+            self.runtime.as_nanos(),
+            memory
+        )
+    }
+}
+
+/// The records in the history file, none if there isn't one yet.
+pub fn load(path: &Path) -> io::Result<Vec<Record>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut records = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        match Record::parse(line) {
+            Some(record) => records.push(record),
+            None => {
+                let message = format!("{}:{}: not a record", path.display(), number + 1);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Adds the records to the end of the history file.
+pub fn append(path: &Path, records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut text = String::new();
+    for record in records {
+        text += &format!("{}\n", record);
+    }
+    file.write_all(text.as_bytes())
+}
+
+/// What a day took on an input: the best runtime of each part over the runs, and the most memory
+/// a run used.
+pub struct Benchmark {
+    pub answers: Vec<Answer>,
+    pub runtimes: Vec<(u32, Duration)>,
+    pub memory: Option<u64>,
+}
+
+pub fn bench(binary: &Path, input: &Path, runs: u32) -> Result<Benchmark, DayError> {
+    let mut benchmark: Option<Benchmark> = None;
+    for _ in 0..runs.max(1) {
+        let (output, memory) = day::output_with_memory(Command::new(binary).arg(input))?;
+        let runtimes = runtimes(&String::from_utf8_lossy(&output.stdout));
+        let answers = day::answers(output)?;
+
+        let Some(best) = &mut benchmark else {
+            benchmark = Some(Benchmark {
+                answers,
+                runtimes,
+                memory,
+            });
+            continue;
+        };
+        for (part, runtime) in runtimes {
+            if let Some(best) = best.runtimes.iter_mut().find(|(p, _)| *p == part) {
+                best.1 = best.1.min(runtime);
+            }
+        }
+        best.memory = best.memory.max(memory);
+    }
+
+    Ok(benchmark.unwrap())
+}
+
+// The runtimes the days print after the answer of each part, "Elapsed: 1.23ms".
+fn runtimes(stdout: &str) -> Vec<(u32, Duration)> {
+    let mut runtimes = Vec::new();
+    let mut part = None;
+    for line in stdout.lines() {
+        if let Some(answer) = Answer::parse(line) {
+            part = Some(answer.part);
+        } else if let Some(elapsed) = line.strip_prefix("Elapsed: ") {
+            if let (Some(part), Some(elapsed)) = (part.take(), parse_duration(elapsed)) {
+                runtimes.push((part, elapsed));
+            }
+        }
+    }
+    runtimes
+}
+
+/// Parses a duration as printed with `{:.2?}`, like "39.53µs".
+fn parse_duration(text: &str) -> Option<Duration> {
+    let split = text.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let unit = match unit {
+        "ns" => 1e-9,
+        "µs" => 1e-6,
+        "ms" => 1e-3,
+        "s" => 1.0,
+        _ => return None,
+    };
+    Some(Duration::from_secs_f64(number * unit))
+}
+
+/// A commit that made a part noticeably slower or faster than the commit measured before it.
+pub struct Change<'a> {
+    pub before: Duration,
+    pub after: &'a Record,
+}
+
+impl Change<'_> {
+    fn describe(&self) -> String {
+        let ratio = self.after.runtime.as_secs_f64() / self.before.as_secs_f64() - 1.0;
+        if ratio > 0.0 {
+            format!("{:.0}% slower", ratio * 100.0)
+        } else {
+            format!("{:.0}% faster", -ratio * 100.0)
+        }
+    }
+}
+
+impl Display for Change<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({:.10}): day {} of {}, part {} {} ({:.2?} -> {:.2?})",
+            self.after.short_commit(),
+            self.after.date,
+            self.after.day,
+            self.after.year,
+            self.after.part,
+            self.describe(),
+            self.before,
+            self.after.runtime
+        )
+    }
+}
+
+// The records of a day on an input, commit by commit: the best runtime of each part, and the most
+// memory. The commits are ordered by date.
+struct Timeline<'a> {
+    parts: Vec<u32>,
+    commits: Vec<(Vec<Option<&'a Record>>, Option<u64>)>,
+}
+
+impl<'a> Timeline<'a> {
+    fn new(records: &[&'a Record]) -> Timeline<'a> {
+        let mut parts: Vec<u32> = records.iter().map(|r| r.part).collect();
+        parts.sort();
+        parts.dedup();
+
+        let mut ids: Vec<(&str, &str)> = records
+            .iter()
+            .map(|r| (r.date.as_str(), r.commit.as_str()))
+            .collect();
+        ids.sort();
+        ids.dedup();
+
+        let commits = ids
+            .iter()
+            .map(|(_, id)| {
+                let of_commit = || records.iter().filter(|r| r.commit == *id);
+                let best = parts
+                    .iter()
+                    .map(|part| {
+                        of_commit()
+                            .filter(|r| r.part == *part)
+                            .min_by_key(|r| r.runtime)
+                            .copied()
+                    })
+                    .collect();
+                let memory = of_commit().filter_map(|r| r.memory).max();
+                (best, memory)
+            })
+            .collect();
+        Timeline { parts, commits }
+    }
+
+    // The changes of each commit from the one before it, for each part.
+    fn changes(&self, threshold: f64) -> Vec<Vec<Option<Change<'a>>>> {
+        let mut previous: Vec<Option<Duration>> = vec![None; self.parts.len()];
+        let mut changes = Vec::new();
+        for (best, _) in &self.commits {
+            let mut row = Vec::new();
+            for (before, record) in previous.iter_mut().zip(best) {
+                let change = match (*before, record) {
+                    (Some(before), Some(after))
+                        if is_noticeable(before, after.runtime, threshold) =>
+                    {
+                        Some(Change { before, after })
+                    }
+                    _ => None,
+                };
+                if let Some(record) = record {
+                    *before = Some(record.runtime);
+                }
+                row.push(change);
+            }
+            changes.push(row);
+        }
+        changes
+    }
+}
+
+fn is_noticeable(before: Duration, after: Duration, threshold: f64) -> bool {
+    let ratio = after.as_secs_f64() / before.as_secs_f64() - 1.0;
+    before.abs_diff(after) >= MIN_CHANGE && ratio.abs() >= threshold
+}
+
+// The runtimes as a line of bars, from the fastest to the slowest.
+fn sparkline(runtimes: &[Duration]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = runtimes.iter().min().copied().unwrap_or_default();
+    let max = runtimes.iter().max().copied().unwrap_or_default();
+    runtimes
+        .iter()
+        .map(|r| {
+            if max == min {
+                return BARS[0];
+            }
+            let height = (*r - min).as_secs_f64() / (max - min).as_secs_f64();
+            BARS[(height * 7.0).round() as usize]
+        })
+        .collect()
+}
+
+pub fn format_memory(memory: Option<u64>) -> String {
+    match memory {
+        Some(bytes) => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        None => "-".to_string(),
+    }
+}
+
+/// Prints the history of the day on each input it was measured on (or just the given one), and
+/// returns the noticeable changes: those by at least `threshold` (0.2 for 20%).
+pub fn report<'a>(
+    records: &'a [Record],
+    day: &Day,
+    input: Option<u64>,
+    threshold: f64,
+) -> Vec<Change<'a>> {
+    let records: Vec<&Record> = records
+        .iter()
+        .filter(|r| r.year == day.year && r.day == day.number)
+        .filter(|r| input.is_none_or(|input| r.input == input))
+        .collect();
+    if records.is_empty() {
+        println!("Nothing recorded");
+        return Vec::new();
+    }
+
+    let mut inputs: Vec<u64> = records.iter().map(|r| r.input).collect();
+    inputs.sort();
+    inputs.dedup();
+
+    let mut noticeable = Vec::new();
+    for input in &inputs {
+        let of_input: Vec<&Record> = records
+            .iter()
+            .filter(|r| r.input == *input)
+            .copied()
+            .collect();
+        if inputs.len() > 1 {
+            println!("Input {:016x}", input);
+        }
+        noticeable.extend(print_timeline(&Timeline::new(&of_input), threshold));
+    }
+    noticeable
+}
+
+fn print_timeline<'a>(timeline: &Timeline<'a>, threshold: f64) -> Vec<Change<'a>> {
+    let changes = timeline.changes(threshold);
+
+    let mut header = vec!["Commit".to_string(), "Date".to_string()];
+    header.extend(timeline.parts.iter().map(|p| format!("Part {}", p)));
+    header.push("Memory".to_string());
+
+    let mut table = vec![header];
+    for ((best, memory), changes) in timeline.commits.iter().zip(&changes) {
+        // Every commit has a record of some part.
+        let any = best.iter().flatten().next().unwrap();
+        let mut cells = vec![any.short_commit(), format!("{:.10}", any.date)];
+        for (record, change) in best.iter().zip(changes) {
+            cells.push(match (record, change) {
+                (Some(record), Some(change)) => {
+                    format!("{:.2?} ({})", record.runtime, change.describe())
+                }
+                (Some(record), None) => format!("{:.2?}", record.runtime),
+                (None, _) => "-".to_string(),
+            });
+        }
+        cells.push(format_memory(*memory));
+        table.push(cells);
+    }
+    compare::print_markdown(&table);
+
+    for (i, part) in timeline.parts.iter().enumerate() {
+        let runtimes: Vec<Duration> = timeline
+            .commits
+            .iter()
+            .filter_map(|(best, _)| best[i].map(|r| r.runtime))
+            .collect();
+        println!("Part {}: {}", part, sparkline(&runtimes));
+    }
+
+    changes.into_iter().flatten().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, date: &str, part: u32, millis: u64) -> Record {
+        Record {
+            commit: commit.to_string(),
+            date: date.to_string(),
+            year: 2023,
+            day: 16,
+            input: 0xabc,
+            part,
+            runtime: Duration::from_millis(millis),
+            memory: Some(2 << 20),
+        }
+    }
+
+    #[test]
+    fn parses_what_the_days_print() {
+        let stdout = "Input: 1.00ms\n\nPart 1: 46\nElapsed: 39.53µs\n\nCycle a: 3\n\
+                      Part 2: 51\nElapsed: 1.20s\n";
+        assert_eq!(
+            runtimes(stdout),
+            [
+                (1, Duration::from_nanos(39530)),
+                (2, Duration::from_millis(1200))
+            ]
+        );
+        assert_eq!(parse_duration("12.00ns"), Some(Duration::from_nanos(12)));
+        assert_eq!(parse_duration("soon"), None);
+    }
+
+    #[test]
+    fn records_round_trip() {
+        let measured = record("5665b64c", "2026-10-18T10:00:00+02:00", 2, 260);
+        assert_eq!(Record::parse(&measured.to_string()), Some(measured));
+        let unknown = Record {
+            memory: None,
+            ..record("5665b64c-dirty", "2026-10-18T10:00:00+02:00", 1, 3)
+        };
+        assert_eq!(Record::parse(&unknown.to_string()), Some(unknown));
+        assert_eq!(Record::parse("5665b64c\t2026-10-18"), None);
+    }
+
+    #[test]
+    fn finds_noticeable_changes() {
+        let records = [
+            record("c1", "2026-10-01T00:00:00Z", 2, 260),
+            record("c3", "2026-10-03T00:00:00Z", 2, 80),
+            record("c2", "2026-10-02T00:00:00Z", 2, 250),
+            // Best of the two.
+            record("c3", "2026-10-03T00:00:00Z", 2, 75),
+            record("c4", "2026-10-04T00:00:00Z", 2, 100),
+        ];
+        let records: Vec<&Record> = records.iter().collect();
+        let timeline = Timeline::new(&records);
+        let changes: Vec<String> = timeline
+            .changes(0.2)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "c3 (2026-10-03): day 16 of 2023, part 2 70% faster (250.00ms -> 75.00ms)",
+                "c4 (2026-10-04): day 16 of 2023, part 2 33% slower (75.00ms -> 100.00ms)",
+            ]
+        );
+        assert_eq!(
+            sparkline(&[260, 250, 75, 100].map(Duration::from_millis)),
+            "██▁▂"
+        );
+    }
+}
@@ -0,0 +1,449 @@
+mod cache;
+mod compare;
+mod day;
+mod external;
+mod history;
+mod registry;
+mod serve;
+mod template;
+
+use aoc_common::cli::{Args, UsageError};
+use cache::Cache;
+use day::{Day, DayError};
+use external::External;
+use history::{Commit, Record};
+use registry::{Entry, Tag};
+use serve::{Puzzle, Solved};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+enum Error {
+    Usage(UsageError),
+    Io(io::Error),
+    DaysFailed(Vec<String>),
+    Registry(DayError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(e) => write!(f, "{}", e),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::DaysFailed(days) => write!(f, "failed: {}", days.join(", ")),
+            Error::Registry(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+const USAGE: &str = "usage: aoc run [--year YYYY] [--day N] [--tag TAG] [--no-cache] [INPUT]
+       aoc compare [--year YYYY] [--day N] [--tag TAG] [--runs N] [INPUT]
+       aoc bench [--year YYYY] [--day N] [--tag TAG] [--runs N] [INPUT]
+       aoc history [--year YYYY] [--day N] [--tag TAG] [--threshold PERCENT] [INPUT]
+       aoc list [--year YYYY] [--day N] [--tag TAG]
+       aoc serve [--port PORT] [--budget SECONDS]
+       aoc new --year YYYY --day N";
+
+// The years live next to the runner, each with a directory per day.
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+// Prints the answers of the day for the input, from the cache if they're in there.
+fn solve(day: &Day, input: &Path, cache: &Cache, use_cache: bool) -> Result<(), DayError> {
+    let binary = day.build()?;
+    let build = cache::hash(&fs::read(&binary).map_err(DayError::Io)?);
+    let input_hash = cache::hash(&fs::read(input).map_err(DayError::Io)?);
+
+    if use_cache {
+        if let Some(answers) = cache.get(day, input_hash, build) {
+            for answer in answers {
+                println!("{}", answer);
+            }
+            println!("Cached");
+            return Ok(());
+        }
+    }
+
+    let start = Instant::now();
+    let answers = Day::run(&binary, input)?;
+    let elapsed = start.elapsed();
+
+    for answer in &answers {
+        println!("{}", answer);
+    }
+    println!("Elapsed: {:.2?}", elapsed);
+
+    cache
+        .put(day, input_hash, build, &answers)
+        .map_err(DayError::Io)?;
+    Ok(())
+}
+
+// The days that were asked for, all days of all years by default, or those with a tag in the
+// registry. An input file only makes sense for a single day.
+fn select_days(mut args: Args) -> Result<(Vec<Day>, Option<PathBuf>), Error> {
+    let year: Option<u32> = args.value("--year").map_err(Error::Usage)?;
+    let day: Option<u32> = args.value("--day").map_err(Error::Usage)?;
+    let tag: Option<Tag> = args.value("--tag").map_err(Error::Usage)?;
+    let input = match args.positional().map_err(Error::Usage)?.as_slice() {
+        [] => None,
+        [path] if day.is_some() => Some(PathBuf::from(path)),
+        _ => return Err(Error::Usage(UsageError(USAGE.to_string()))),
+    };
+
+    let root = root();
+    let years = match year {
+        Some(year) => vec![year],
+        None => day::years(&root).map_err(Error::Io)?,
+    };
+    let numbers: Vec<u32> = match day {
+        Some(number) => vec![number],
+        None => (1..=25).collect(),
+    };
+    let mut days: Vec<Day> = years
+        .iter()
+        .flat_map(|year| numbers.iter().map(|number| Day::new(&root, *year, *number)))
+        .filter(|day| day.exists())
+        .collect();
+    if let Some(tag) = tag {
+        let mut tagged = Vec::new();
+        for day in days {
+            let entry = Entry::load(&day).map_err(Error::Registry)?;
+            if entry.is_some_and(|e| e.has_tag(tag)) {
+                tagged.push(day);
+            }
+        }
+        days = tagged;
+    }
+    if days.is_empty() {
+        return Err(Error::Usage(UsageError("no such day".to_string())));
+    }
+
+    Ok((days, input))
+}
+
+// The input of the day, unless it has none and wasn't asked for explicitly.
+fn input_of(day: &Day, input: &Option<PathBuf>, several_days: bool) -> Option<PathBuf> {
+    let input = input.clone().unwrap_or_else(|| day.default_input());
+    if several_days && input.metadata().map_or(true, |m| m.len() == 0) {
+        return None;
+    }
+    Some(input)
+}
+
+fn run(mut args: Args) -> Result<(), Error> {
+    let use_cache = !args.flag("--no-cache");
+    let (days, input) = select_days(args)?;
+
+    let cache = Cache::new(root().join(".aoc-cache"));
+    let mut failed = Vec::new();
+
+    for day in &days {
+        let Some(input) = input_of(day, &input, days.len() > 1) else {
+            continue;
+        };
+
+        println!("{}", day);
+        if let Err(e) = solve(day, &input, &cache, use_cache) {
+            println!("Failed: {}", e);
+            failed.push(day.to_string());
+        }
+        println!();
+    }
+
+    if !failed.is_empty() {
+        return Err(Error::DaysFailed(failed));
+    }
+    Ok(())
+}
+
+// Compares the day to its external solutions, failing if they don't agree.
+fn compare_day(day: &Day, input: &Path, runs: u32) -> Result<bool, DayError> {
+    let externals = External::load(day.year_dir())?;
+    let rows = compare::compare(day, &externals, input, runs)?;
+    compare::print_table(&rows, runs);
+
+    if rows.len() == 1 {
+        println!("Nothing to compare");
+    } else if compare::agree(&rows) {
+        println!("All {} solutions agree", rows.len());
+    } else {
+        println!("SOLUTIONS DISAGREE");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn compare(mut args: Args) -> Result<(), Error> {
+    let runs: u32 = args.value("--runs").map_err(Error::Usage)?.unwrap_or(5);
+    let (days, input) = select_days(args)?;
+
+    let mut failed = Vec::new();
+    for day in &days {
+        let Some(input) = input_of(day, &input, days.len() > 1) else {
+            continue;
+        };
+
+        println!("{}", day);
+        match compare_day(day, &input, runs) {
+            Ok(true) => {}
+            Ok(false) => failed.push(day.to_string()),
+            Err(e) => {
+                println!("Failed: {}", e);
+                failed.push(day.to_string());
+            }
+        }
+        println!();
+    }
+
+    if !failed.is_empty() {
+        return Err(Error::DaysFailed(failed));
+    }
+    Ok(())
+}
+
+// Measures the day and prints the results, as records for the history.
+fn bench_day(day: &Day, input: &Path, runs: u32, commit: &Commit) -> Result<Vec<Record>, DayError> {
+    let binary = day.build()?;
+    let input_hash = cache::hash(&fs::read(input).map_err(DayError::Io)?);
+    let benchmark = history::bench(&binary, input, runs)?;
+
+    for answer in &benchmark.answers {
+        println!("{}", answer);
+    }
+    let mut records = Vec::new();
+    for (part, runtime) in &benchmark.runtimes {
+        println!("Part {}: best of {} in {:.2?}", part, runs.max(1), runtime);
+        records.push(Record {
+            commit: commit.id.clone(),
+            date: commit.date.clone(),
+            year: day.year,
+            day: day.number,
+            input: input_hash,
+            part: *part,
+            runtime: *runtime,
+            memory: benchmark.memory,
+        });
+    }
+    println!("Memory: {}", history::format_memory(benchmark.memory));
+    Ok(records)
+}
+
+// Measures the days and adds the results to the history, along with the commit.
+fn bench(mut args: Args) -> Result<(), Error> {
+    let runs: u32 = args.value("--runs").map_err(Error::Usage)?.unwrap_or(5);
+    let (days, input) = select_days(args)?;
+
+    let root = root();
+    let commit = history::current_commit(&root).map_err(Error::Io)?;
+    let path = root.join(".aoc-history");
+    let mut recorded = 0;
+    let mut failed = Vec::new();
+    for day in &days {
+        let Some(input) = input_of(day, &input, days.len() > 1) else {
+            continue;
+        };
+
+        println!("{}", day);
+        match bench_day(day, &input, runs, &commit) {
+            Ok(records) => {
+                history::append(&path, &records).map_err(Error::Io)?;
+                recorded += 1;
+            }
+            Err(e) => {
+                println!("Failed: {}", e);
+                failed.push(day.to_string());
+            }
+        }
+        println!();
+    }
+    if recorded > 0 {
+        println!(
+            "Recorded {} day(s) for commit {} in {}",
+            recorded,
+            commit.id,
+            path.display()
+        );
+    }
+
+    if !failed.is_empty() {
+        return Err(Error::DaysFailed(failed));
+    }
+    Ok(())
+}
+
+// Prints how the days changed over the commits in the history.
+fn history(mut args: Args) -> Result<(), Error> {
+    let threshold: f64 = args
+        .value("--threshold")
+        .map_err(Error::Usage)?
+        .unwrap_or(20.0);
+    let (days, input) = select_days(args)?;
+    let input = match input {
+        Some(path) => Some(cache::hash(&fs::read(path).map_err(Error::Io)?)),
+        None => None,
+    };
+
+    let records = history::load(&root().join(".aoc-history")).map_err(Error::Io)?;
+    let mut noticeable = Vec::new();
+    for day in &days {
+        let recorded = records
+            .iter()
+            .any(|r| r.year == day.year && r.day == day.number);
+        if !recorded && days.len() > 1 {
+            continue;
+        }
+
+        println!("{}", day);
+        noticeable.extend(history::report(&records, day, input, threshold / 100.0));
+        println!();
+    }
+
+    if noticeable.is_empty() {
+        println!("No noticeable changes (by {}% or more)", threshold);
+    } else {
+        println!("Noticeable changes (by {}% or more):", threshold);
+        for change in noticeable {
+            println!("{}", change);
+        }
+    }
+    Ok(())
+}
+
+// Prints the registry entries of the days.
+fn list(args: Args) -> Result<(), Error> {
+    let (days, input) = select_days(args)?;
+    if input.is_some() {
+        return Err(Error::Usage(UsageError(USAGE.to_string())));
+    }
+
+    for day in &days {
+        match Entry::load(day).map_err(Error::Registry)? {
+            Some(entry) => println!("{}: {}\n", day, entry),
+            None => println!("{}: not registered\n", day),
+        }
+    }
+    Ok(())
+}
+
+// Solves a part for `aoc serve`. The days always solve both parts, so the time is that of both.
+fn solve_puzzle(root: &Path, budget: Duration, puzzle: &Puzzle, input: &str) -> Solved {
+    let day = Day::new(root, puzzle.year, puzzle.day);
+    if !day.exists() {
+        return Solved::UnknownDay;
+    }
+
+    let solved = (|| {
+        let binary = day.build()?;
+        let path = std::env::temp_dir().join(format!("aoc-serve-{}.txt", std::process::id()));
+        fs::write(&path, input).map_err(DayError::Io)?;
+
+        let start = Instant::now();
+        let answers = Day::run_within(&binary, &path, budget);
+        let elapsed = start.elapsed();
+        let _ = fs::remove_file(&path);
+
+        let answers = answers?;
+        Ok(match day::answer_of(&answers, puzzle.part) {
+            Some(answer) => Solved::Answer(answer.value.clone(), elapsed),
+            None => Solved::NoAnswer,
+        })
+    })();
+
+    let solved = match solved {
+        Ok(solved) => solved,
+        Err(DayError::OutOfTime(budget)) => Solved::OutOfTime(budget),
+        Err(e) => Solved::Failed(e.to_string()),
+    };
+    match &solved {
+        Solved::Answer(answer, elapsed) => {
+            println!(
+                "{}, part {}: {} in {:.2?}",
+                day, puzzle.part, answer, elapsed
+            )
+        }
+        _ => println!("{}, part {}: not solved", day, puzzle.part),
+    }
+    solved
+}
+
+fn serve(mut args: Args) -> Result<(), Error> {
+    let port: u16 = args.value("--port").map_err(Error::Usage)?.unwrap_or(8023);
+    let budget: f64 = args
+        .value("--budget")
+        .map_err(Error::Usage)?
+        .unwrap_or(30.0);
+    args.finish().map_err(Error::Usage)?;
+
+    let root = root();
+    let budget = Duration::from_secs_f64(budget);
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(Error::Io)?;
+    println!(
+        "Listening on http://127.0.0.1:{}, POST /YEAR/day/N/part/P",
+        port
+    );
+
+    serve::serve(listener, |puzzle, input| {
+        solve_puzzle(&root, budget, puzzle, input)
+    })
+    .map_err(Error::Io)?;
+    Ok(())
+}
+
+fn new_day(mut args: Args) -> Result<(), Error> {
+    let usage = || Error::Usage(UsageError(USAGE.to_string()));
+    let year: u32 = args
+        .value("--year")
+        .map_err(Error::Usage)?
+        .ok_or_else(usage)?;
+    let day: u32 = args
+        .value("--day")
+        .map_err(Error::Usage)?
+        .ok_or_else(usage)?;
+    args.finish().map_err(Error::Usage)?;
+
+    let root = root();
+    let day = Day::new(&root, year, day);
+    template::create_day(&root.join("template"), &day).map_err(Error::Io)?;
+
+    println!("Created {} in {}", day, day.dir().display());
+    Ok(())
+}
+
+fn command() -> Result<(), Error> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        return Err(Error::Usage(UsageError(USAGE.to_string())));
+    }
+
+    let command = args.remove(0);
+    match command.as_str() {
+        "run" => run(Args::new(args)),
+        "compare" => compare(Args::new(args)),
+        "bench" => bench(Args::new(args)),
+        "history" => history(Args::new(args)),
+        "list" => list(Args::new(args)),
+        "serve" => serve(Args::new(args)),
+        "new" => new_day(Args::new(args)),
+        _ => Err(Error::Usage(UsageError(format!(
+            "unknown command {}",
+            command
+        )))),
+    }
+}
+
+fn main() -> ExitCode {
+    match command() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! What the days solve, and how. Each day declares it in a `puzzle.txt` next to its manifest, with a
+//! line "key: value" for the title, a short description of each part, the techniques it uses (as
+//! tags) and how long it takes on a real input:
+//!
+//! ```text
+//! title: Haunted Wasteland
+//! part 1: Steps to follow the left/right instructions through the network from AAA to ZZZ.
+//! part 2: Steps until all ghosts, starting on the nodes ending in A, are on nodes ending in Z.
+//! tags: graph
+//! runtime: instant
+//! ```
+
+use crate::day::{Day, DayError};
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tag {
+    Grid,
+    Dp,
+    Graph,
+    Geometry,
+    Simulation,
+    Randomized,
+}
+
+const TAGS: [(&str, Tag); 6] = [
+    ("grid", Tag::Grid),
+    ("dp", Tag::Dp),
+    ("graph", Tag::Graph),
+    ("geometry", Tag::Geometry),
+    ("simulation", Tag::Simulation),
+    ("randomized", Tag::Randomized),
+];
+
+impl FromStr for Tag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match TAGS.iter().find(|(name, _)| *name == s) {
+            Some((_, tag)) => Ok(*tag),
+            None => Err(format!("unknown tag {}", s)),
+        }
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (name, _) = TAGS.iter().find(|(_, tag)| tag == self).unwrap();
+        write!(f, "{}", name)
+    }
+}
+
+/// How long a day takes for both parts, roughly, in a release build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runtime {
+    /// Less than 10ms.
+    Instant,
+    /// Less than a second.
+    Fast,
+    /// Anything longer.
+    Slow,
+}
+
+impl FromStr for Runtime {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "instant" => Ok(Runtime::Instant),
+            "fast" => Ok(Runtime::Fast),
+            "slow" => Ok(Runtime::Slow),
+            _ => Err(format!("unknown runtime {}", s)),
+        }
+    }
+}
+
+impl Display for Runtime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Runtime::Instant => "instant",
+            Runtime::Fast => "fast",
+            Runtime::Slow => "slow",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+pub struct Entry {
+    pub title: String,
+    pub part1: String,
+    pub part2: String,
+    pub tags: Vec<Tag>,
+    pub runtime: Runtime,
+}
+
+impl Entry {
+    fn parse(text: &str) -> Result<Entry, String> {
+        let (mut title, mut part1, mut part2, mut tags, mut runtime) =
+            (None, None, None, None, None);
+        for line in text.lines().map(|l| l.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(format!("not a \"key: value\" line: {}", line));
+            };
+            let value = value.trim();
+            match key {
+                "title" => title = Some(value.to_string()),
+                "part 1" => part1 = Some(value.to_string()),
+                "part 2" => part2 = Some(value.to_string()),
+                "tags" => {
+                    let parsed: Result<Vec<Tag>, String> = value
+                        .split(',')
+                        .map(|t| t.trim())
+                        .filter(|t| !t.is_empty())
+                        .map(|t| t.parse())
+                        .collect();
+                    tags = Some(parsed?);
+                }
+                "runtime" => runtime = Some(value.parse()?),
+                _ => return Err(format!("unknown key {}", key)),
+            }
+        }
+
+        let missing = |key: &str| format!("{} is missing", key);
+        Ok(Entry {
+            title: title.ok_or_else(|| missing("title"))?,
+            part1: part1.ok_or_else(|| missing("part 1"))?,
+            part2: part2.ok_or_else(|| missing("part 2"))?,
+            tags: tags.unwrap_or_default(),
+            runtime: runtime.ok_or_else(|| missing("runtime"))?,
+        })
+    }
+
+    /// The entry of the day, if it has one.
+    pub fn load(day: &Day) -> Result<Option<Entry>, DayError> {
+        let path = day.dir().join("puzzle.txt");
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(DayError::Io(e)),
+        };
+
+        Entry::parse(&text)
+            .map(Some)
+            .map_err(|e| DayError::InvalidEntry(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn has_tag(&self, tag: Tag) -> bool {
+        self.tags.contains(&tag)
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let tags: Vec<String> = self.tags.iter().map(|t| t.to_string()).collect();
+        writeln!(f, "{} ({})", self.title, self.runtime)?;
+        writeln!(f, "  Part 1: {}", self.part1)?;
+        writeln!(f, "  Part 2: {}", self.part2)?;
+        write!(
+            f,
+            "  Tags: {}",
+            if tags.is_empty() {
+                "-".to_string()
+            } else {
+                tags.join(", ")
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_an_entry() {
+        let entry = Entry::parse(
+            "# A comment.\ntitle: Step Counter\npart 1: Plots.\npart 2: More plots.\n\
+             tags: grid, simulation\nruntime: fast\n",
+        )
+        .unwrap();
+        assert_eq!(entry.title, "Step Counter");
+        assert_eq!(entry.tags, [Tag::Grid, Tag::Simulation]);
+        assert_eq!(entry.runtime, Runtime::Fast);
+        assert!(entry.has_tag(Tag::Grid));
+        assert!(!entry.has_tag(Tag::Dp));
+
+        assert!(
+            Entry::parse("title: A\npart 1: B\npart 2: C\ntags: magic\nruntime: fast\n").is_err()
+        );
+        assert!(Entry::parse("title: A\npart 1: B\ntags:\nruntime: fast\n").is_err());
+    }
+
+    #[test]
+    fn every_day_is_registered() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for year in crate::day::years(&root).unwrap() {
+            for number in 1..=25 {
+                let day = Day::new(&root, year, number);
+                if day.exists() {
+                    assert!(Entry::load(&day).unwrap().is_some(), "{} has no entry", day);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,369 @@
+//! `aoc serve`: solves inputs sent over HTTP, for those who'd rather not build the days
+//! themselves. Only listens on localhost, and handles one request at a time (building and running
+//! the days in parallel wouldn't be faster anyway).
+//!
+//! `POST /2023/day/7/part/1` with the input as the body answers with
+//! `{"year":2023,"day":7,"part":1,"answer":"6440","elapsed_ms":1.53}`, errors with
+//! `{"error":"…"}` and a fitting status.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+/// The largest input that is accepted, real inputs are a few kB.
+const MAX_INPUT: usize = 10 << 20;
+
+/// The longest request line, and the most bytes of all headers together.
+const MAX_LINE: usize = 8 << 10;
+const MAX_HEADERS: usize = 64 << 10;
+
+/// How long a client may take to send the next part of its request. Requests are answered one at
+/// a time, so a client that stops sending would hold up everyone else.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+}
+
+/// What solving an input came up with.
+pub enum Solved {
+    Answer(String, Duration),
+    UnknownDay,
+    NoAnswer,
+    OutOfTime(Duration),
+    Failed(String),
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            408 => "Request Timeout",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            411 => "Length Required",
+            413 => "Content Too Large",
+            414 => "URI Too Long",
+            431 => "Request Header Fields Too Large",
+            500 => "Internal Server Error",
+            504 => "Gateway Timeout",
+            _ => "Unknown",
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            '\n' => json += "\\n",
+            '\r' => json += "\\r",
+            '\t' => json += "\\t",
+            c if (c as u32) < 0x20 => json += &format!("\\u{:04x}", c as u32),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// "/2023/day/7/part/1"
+fn route(path: &str) -> Option<Puzzle> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        [year, "day", day, "part", part] => Some(Puzzle {
+            year: year.parse().ok()?,
+            day: day.parse().ok()?,
+            part: part.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
+
+// Reads a line of at most `limit` bytes into `line`, `false` if it's longer.
+fn read_line(reader: &mut impl BufRead, line: &mut String, limit: usize) -> io::Result<bool> {
+    line.clear();
+    reader.take(limit as u64 + 1).read_line(line)?;
+    Ok(line.len() <= limit)
+}
+
+// Answers a request, or with 408 if the client stops sending it for longer than `timeout`.
+fn handle<F>(stream: &mut TcpStream, timeout: Duration, solve: &F) -> io::Result<Response>
+where
+    F: Fn(&Puzzle, &str) -> Solved,
+{
+    stream.set_read_timeout(Some(timeout))?;
+    match answer(stream, solve) {
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+            ) =>
+        {
+            Ok(Response::error(408, "the request wasn't sent in time"))
+        }
+        result => result,
+    }
+}
+
+// Reads a request (line, headers, body) and answers it. Errors of the request itself are returned
+// as a response, errors of the connection as an error.
+fn answer<F>(stream: &mut TcpStream, solve: &F) -> io::Result<Response>
+where
+    F: Fn(&Puzzle, &str) -> Solved,
+{
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    if !read_line(&mut reader, &mut line, MAX_LINE)? {
+        return Ok(Response::error(414, "the request line is too long"));
+    }
+    let mut request = line.split_whitespace();
+    let (Some(method), Some(path)) = (request.next(), request.next()) else {
+        return Ok(Response::error(400, "invalid request"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut length = None;
+    let mut left = MAX_HEADERS;
+    loop {
+        if !read_line(&mut reader, &mut line, left)? {
+            return Ok(Response::error(431, "the headers are too large"));
+        }
+        left -= line.len();
+        if line.is_empty() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+
+    let Some(puzzle) = route(&path) else {
+        return Ok(Response::error(404, "expected /YEAR/day/N/part/P"));
+    };
+    if method != "POST" {
+        return Ok(Response::error(405, "the input has to be POSTed"));
+    }
+    let Some(length) = length else {
+        return Ok(Response::error(411, "missing Content-Length"));
+    };
+    if length > MAX_INPUT {
+        return Ok(Response::error(413, "the input is too large"));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    let Ok(input) = String::from_utf8(body) else {
+        return Ok(Response::error(400, "the input isn't UTF-8"));
+    };
+
+    let response = match solve(&puzzle, &input) {
+        Solved::Answer(answer, elapsed) => Response {
+            status: 200,
+            body: format!(
+                "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"elapsed_ms\":{:.3}}}",
+                puzzle.year,
+                puzzle.day,
+                puzzle.part,
+                json_string(&answer),
+                elapsed.as_secs_f64() * 1000.0
+            ),
+        },
+        Solved::UnknownDay => Response::error(
+            404,
+            &format!("there's no day {} of {}", puzzle.day, puzzle.year),
+        ),
+        Solved::NoAnswer => Response::error(
+            404,
+            &format!("day {} has no part {}", puzzle.day, puzzle.part),
+        ),
+        Solved::OutOfTime(budget) => {
+            Response::error(504, &format!("not solved within {:.2?}", budget))
+        }
+        Solved::Failed(error) => Response::error(500, &error),
+    };
+    Ok(response)
+}
+
+fn respond(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Answers requests until the listener fails. A connection that fails is only reported.
+pub fn serve<F>(listener: TcpListener, solve: F) -> io::Result<()>
+where
+    F: Fn(&Puzzle, &str) -> Solved,
+{
+    serve_with_timeout(listener, READ_TIMEOUT, solve)
+}
+
+fn serve_with_timeout<F>(listener: TcpListener, timeout: Duration, solve: F) -> io::Result<()>
+where
+    F: Fn(&Puzzle, &str) -> Solved,
+{
+    for stream in listener.incoming() {
+        let mut stream = stream?;
+        let result = handle(&mut stream, timeout, &solve).and_then(|r| respond(&mut stream, &r));
+        if let Err(e) = result {
+            eprintln!("Failed to answer a request: {}", e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    // Answers part 1 of day 1 with the length of the input, and takes its time for day 2.
+    fn stub(puzzle: &Puzzle, input: &str) -> Solved {
+        match (puzzle.year, puzzle.day, puzzle.part) {
+            (2023, 1, 1) => Solved::Answer(input.len().to_string(), Duration::from_millis(2)),
+            (2023, 1, _) => Solved::NoAnswer,
+            (2023, 2, _) => Solved::OutOfTime(Duration::from_secs(1)),
+            (2023, 3, _) => Solved::Failed("ParseError \"line 1\"".to_string()),
+            _ => Solved::UnknownDay,
+        }
+    }
+
+    fn start() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let timeout = Duration::from_millis(200);
+        thread::spawn(move || serve_with_timeout(listener, timeout, stub));
+        port
+    }
+
+    // Sends the raw request and returns the status and body of the response.
+    fn request(port: u16, request: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        assert!(head.contains(&format!("Content-Length: {}", body.len())));
+        (status, body.to_string())
+    }
+
+    fn post(port: u16, path: &str, input: &str) -> (u16, String) {
+        request(
+            port,
+            &format!(
+                "POST {} HTTP/1.1\r\nHost: localhost\r\ncontent-length: {}\r\n\r\n{}",
+                path,
+                input.len(),
+                input
+            ),
+        )
+    }
+
+    #[test]
+    fn answers_with_json() {
+        let port = start();
+        assert_eq!(
+            post(port, "/2023/day/1/part/1", "1abc2\npqr3stu8vwx\n"),
+            (
+                200,
+                "{\"year\":2023,\"day\":1,\"part\":1,\"answer\":\"18\",\"elapsed_ms\":2.000}"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_days_and_parts() {
+        let port = start();
+        assert_eq!(
+            post(port, "/2023/day/26/part/1", "x"),
+            (404, "{\"error\":\"there's no day 26 of 2023\"}".to_string())
+        );
+        assert_eq!(
+            post(port, "/2023/day/1/part/3", "x"),
+            (404, "{\"error\":\"day 1 has no part 3\"}".to_string())
+        );
+        assert_eq!(post(port, "/2023/day/one/part/1", "x").0, 404);
+        assert_eq!(post(port, "/2023/day/1", "x").0, 404);
+    }
+
+    #[test]
+    fn reports_failures() {
+        let port = start();
+        assert_eq!(
+            post(port, "/2023/day/2/part/1", "x"),
+            (504, "{\"error\":\"not solved within 1.00s\"}".to_string())
+        );
+        assert_eq!(
+            post(port, "/2023/day/3/part/1", "x"),
+            (500, "{\"error\":\"ParseError \\\"line 1\\\"\"}".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let port = start();
+        let get = "GET /2023/day/1/part/1 HTTP/1.1\r\n\r\n";
+        assert_eq!(request(port, get).0, 405);
+        let no_length = "POST /2023/day/1/part/1 HTTP/1.1\r\n\r\n";
+        assert_eq!(request(port, no_length).0, 411);
+        let too_large = "POST /2023/day/1/part/1 HTTP/1.1\r\nContent-Length: 999999999\r\n\r\n";
+        assert_eq!(request(port, too_large).0, 413);
+        assert_eq!(request(port, "\r\n").0, 400);
+    }
+
+    #[test]
+    fn rejects_oversized_requests() {
+        let port = start();
+        let long_path = format!("POST /{} HTTP/1.1\r\n\r\n", "x".repeat(MAX_LINE));
+        assert_eq!(request(port, &long_path).0, 414);
+        let long_header = format!(
+            "POST /2023/day/1/part/1 HTTP/1.1\r\nX-Long: {}\r\n\r\n",
+            "x".repeat(MAX_HEADERS)
+        );
+        assert_eq!(request(port, &long_header).0, 431);
+    }
+
+    #[test]
+    fn times_out_clients_that_stop_sending() {
+        let port = start();
+        let mut stalled = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let halfway = "POST /2023/day/1/part/1 HTTP/1.1\r\nContent-Length: 10\r\n\r\n1ab";
+        stalled.write_all(halfway.as_bytes()).unwrap();
+
+        // Answered once the stalled client has timed out, instead of waiting behind it forever.
+        assert_eq!(post(port, "/2023/day/1/part/1", "1abc2\n").0, 200);
+
+        let mut response = String::new();
+        stalled.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 408 Request Timeout\r\n"));
+    }
+}
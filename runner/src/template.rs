@@ -0,0 +1,59 @@
+//! New days start as a copy of the template crate.
+
+use crate::day::Day;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Copies the template into the directory of the day, naming the crate after it. The day starts out
+/// with an empty input, and a registry entry to fill in.
+pub fn create_day(template: &Path, day: &Day) -> io::Result<()> {
+    if day.dir().exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", day.dir().display()),
+        ));
+    }
+
+    // The template sits next to the common library, so it builds in place. The days are a level
+    // deeper, in the directory of their year.
+    let manifest = fs::read_to_string(template.join("Cargo.toml"))?;
+    let manifest = manifest
+        .replace(
+            "name = \"template\"",
+            &format!("name = \"day{}\"", day.number),
+        )
+        .replace("path = \"../common\"", "path = \"../../common\"");
+
+    fs::create_dir_all(day.dir().join("src"))?;
+    fs::create_dir_all(day.dir().join("rsc"))?;
+    fs::write(day.dir().join("Cargo.toml"), manifest)?;
+    fs::copy(
+        template.join("src").join("main.rs"),
+        day.dir().join("src").join("main.rs"),
+    )?;
+    fs::copy(template.join("puzzle.txt"), day.dir().join("puzzle.txt"))?;
+    fs::write(day.default_input(), "")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_find_the_common_library() {
+        let template = Path::new(env!("CARGO_MANIFEST_DIR")).join("../template");
+        let root = std::env::temp_dir().join(format!("aoc-template-{}", std::process::id()));
+        let day = Day::new(&root, 2099, 3);
+        create_day(&template, &day).unwrap();
+
+        let manifest = fs::read_to_string(day.dir().join("Cargo.toml")).unwrap();
+        assert!(manifest.contains("name = \"day3\""));
+        assert!(manifest.contains("aoc-common = { path = \"../../common\" }"));
+        assert_eq!(fs::read_to_string(day.default_input()).unwrap(), "");
+        assert!(create_day(&template, &day).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,7 +1,22 @@
+use aoc_common::cli::Args;
+use aoc_common::stream::{Source, StreamError};
+use std::fmt::Display;
 use std::time::Instant;
 
 #[derive(Debug)]
-enum Error {}
+enum Error {
+    StreamError(StreamError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::StreamError(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
 
 fn part1(input: &str) -> Result<(), Error> {
     println!("Part 1: TBD");
@@ -14,14 +29,17 @@ fn part2(input: &str) -> Result<(), Error> {
 }
 
 fn main() -> Result<(), Error> {
-    let input = include_str!("../rsc/sample1.txt");
+    let args = Args::from_env();
+    let input = Source::from_args(args, include_str!("../rsc/input.txt"))
+        .and_then(|source| source.read())
+        .map_err(Error::StreamError)?;
 
     let start1 = Instant::now();
-    part1(input)?;
+    part1(&input)?;
     println!("Elapsed: {:.2?}\n", start1.elapsed());
 
     let start2 = Instant::now();
-    part2(input)?;
+    part2(&input)?;
     println!("Elapsed: {:.2?}", start2.elapsed());
 
     Ok(())